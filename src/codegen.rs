@@ -0,0 +1,123 @@
+//! Generates Rust struct and enum source from an Ardite Schema Definition, so
+//! driver results can be deserialized into compile-time-checked types instead
+//! of untyped `Value`.
+//!
+//! The live `schema::Schema` has no getters to introspect a built definition
+//! back out, so generation instead walks the `SerdeDefinition`/`SerdeSchema`
+//! intermediary tree that `definition::serde::parse_file` parses files
+//! into—the same tree `SerdeSchema::to_schema` lowers, just not yet lowered.
+
+use std::io::Write;
+
+use case::Case;
+use error::Error;
+use definition::serde::{SerdeDefinition, SerdeSchema};
+
+/// Walks `definition`'s root object and writes one Rust type per top-level
+/// property to `out`: `object` schemas become a named struct (with
+/// `Option<T>` fields for properties missing from `required`), `enum`
+/// schemas become a named Rust `enum`, and everything else becomes a `pub
+/// type` alias. Nested `object` and `enum` properties are generated as their
+/// own named types, prefixed with their containing type's name.
+///
+/// The scalar mapping mirrors `SerdeSchema::to_schema`: `number` becomes
+/// `f64` (or `i64` when `multiple_of == 1.0`), `string` becomes `String`,
+/// `boolean` becomes `bool`, and `array` becomes `Vec<T>`.
+pub fn generate<W: Write>(definition: &SerdeDefinition, out: &mut W) -> Result<(), Error> {
+  for (name, schema) in definition.data.properties.clone().unwrap_or_default() {
+    try!(generate_named_type(&Case::Class.to_case(name), &schema, out));
+  }
+  Ok(())
+}
+
+/// Writes the named struct, enum, or type alias for `schema` to `out`.
+fn generate_named_type<W: Write>(name: &str, schema: &SerdeSchema, out: &mut W) -> Result<(), Error> {
+  if schema.type_.as_ref().map(String::as_str) == Some("object") {
+    generate_struct(name, schema, out)
+  } else if schema.type_.is_none() {
+    match schema.enum_ {
+      Some(ref symbols) => generate_enum(name, symbols, out),
+      None => Err(Error::invalid("No schema type specified.", "Set a `type` property or an `enum` property."))
+    }
+  } else {
+    let rust_type = try!(schema_to_rust_type(schema));
+    try!(writeln!(out, "pub type {} = {};\n", name, rust_type));
+    Ok(())
+  }
+}
+
+/// Writes a derived struct for an `object` schema, generating nested structs
+/// or enums for any `object`/`enum` properties before the struct that uses
+/// them.
+fn generate_struct<W: Write>(name: &str, schema: &SerdeSchema, out: &mut W) -> Result<(), Error> {
+  let required = schema.required.clone().unwrap_or_default();
+  let mut fields = Vec::new();
+
+  for (key, property) in schema.properties.clone().unwrap_or_default() {
+    let is_nested = property.type_.as_ref().map(String::as_str) == Some("object")
+      || (property.type_.is_none() && property.enum_.is_some());
+
+    let rust_type = if is_nested {
+      let nested_name = format!("{}{}", name, Case::Class.to_case(key.clone()));
+      try!(generate_named_type(&nested_name, &property, out));
+      nested_name
+    } else {
+      try!(schema_to_rust_type(&property))
+    };
+
+    let rust_type = if required.contains(&key) { rust_type } else { format!("Option<{}>", rust_type) };
+
+    fields.push(format!("  pub {}: {},", Case::Snake.to_case(key), rust_type));
+  }
+
+  try!(writeln!(out, "#[derive(Serialize, Deserialize, Debug)]"));
+  try!(writeln!(out, "pub struct {} {{", name));
+  for field in fields {
+    try!(writeln!(out, "{}", field));
+  }
+  try!(writeln!(out, "}}\n"));
+  Ok(())
+}
+
+/// Writes a derived enum for an `enum` schema, one variant per symbol.
+fn generate_enum<W: Write>(name: &str, symbols: &[String], out: &mut W) -> Result<(), Error> {
+  try!(writeln!(out, "#[derive(Serialize, Deserialize, Debug)]"));
+  try!(writeln!(out, "pub enum {} {{", name));
+  for symbol in symbols {
+    try!(writeln!(out, "  {},", Case::Class.to_case(symbol.clone())));
+  }
+  try!(writeln!(out, "}}\n"));
+  Ok(())
+}
+
+/// Maps a schema to the Rust type which represents its values. Used for
+/// schemas which don't need a named struct or enum of their own: scalars,
+/// and the item type of an array.
+fn schema_to_rust_type(schema: &SerdeSchema) -> Result<String, Error> {
+  match schema.type_ {
+    Some(ref type_) => match type_.as_str() {
+      "null" => Ok("()".to_owned()),
+      "boolean" => Ok("bool".to_owned()),
+      "number" | "integer" => {
+        if type_ == "integer" || schema.multiple_of == Some(1.0) {
+          Ok("i64".to_owned())
+        } else {
+          Ok("f64".to_owned())
+        }
+      },
+      "string" => Ok("String".to_owned()),
+      "array" => {
+        let items = try!(schema.items.as_ref().ok_or_else(|| Error::invalid(
+          "Array schema is missing an 'items' type.",
+          "Set an 'items' property describing the array's elements."
+        )));
+        Ok(format!("Vec<{}>", try!(schema_to_rust_type(items))))
+      },
+      _ => Err(Error::invalid(
+        format!("Cannot generate a Rust type for schema type '{}' outside of a named struct.", type_),
+        "Nest 'object' and 'enum' schemas as named properties instead of using them inline."
+      ))
+    },
+    None => Err(Error::invalid("No schema type specified.", "Set a `type` property or an `enum` property."))
+  }
+}
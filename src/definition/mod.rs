@@ -5,6 +5,8 @@
 pub mod schema;
 pub mod serde;
 
+pub use self::serde::from_file;
+
 use definition::schema::Schema;
 
 /// The definition object which contains all necessary information to
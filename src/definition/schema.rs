@@ -88,12 +88,54 @@ pub enum Schema {
   /// Represents a value which *must* be one of the defined values. An enum is
   /// considered a primitive type as if it is a single value is a higher order
   /// type, no variation is allowed.
-  Enum(Vec<Value>)
+  Enum(Vec<Value>),
+  /// Represents a discriminated union of object schemas, giving Rust-enum-
+  /// style polymorphic documents without the unconstrained `oneOf` of JSON
+  /// Schema. Unconstrained `oneOf` is excluded for the same reason listed
+  /// above `Schema` itself: it breaks searchability, since there would be no
+  /// single schema to return for a pointer without first knowing which
+  /// branch a concrete value took.
+  ///
+  /// `OneOf` stays searchable by naming a single `discriminator` property
+  /// whose value picks the branch: `get` can answer for the discriminator
+  /// itself (an `Enum` of the possible tags) and, for any other property,
+  /// answers only when every branch agrees on the schema there.
+  OneOf {
+    /// The object property whose value selects which variant a concrete
+    /// value belongs to.
+    discriminator: Key,
+    /// The possible variants, keyed by the value the discriminator takes for
+    /// that variant.
+    variants: LinearMap<Value, Schema>
+  },
+  /// Represents a value which must match at least one of the given schemas.
+  /// Unlike `OneOf`, there is no discriminator, so `get` can only answer for
+  /// a pointer every schema happens to agree on.
+  AnyOf(Vec<Schema>),
+  /// Represents a value which must match every one of the given schemas.
+  AllOf(Vec<Schema>),
+  /// Stands in for another schema declared elsewhere in the same definition,
+  /// addressed by a `get`-style pointer from the definition's root. Stored
+  /// lazily—just the pointer, not the schema it points to—so a type that
+  /// refers to itself (directly or transitively) doesn't recurse forever
+  /// while the definition is being built; resolution happens each time the
+  /// reference is walked instead.
+  Reference(Pointer),
+  /// Represents a value which must *not* match the wrapped schema.
+  Not(Box<Schema>)
 }
 
 impl Schema {
   /// Gets a nested schema at a certain point.
-  pub fn get(&self, mut pointer: Pointer) -> Self {
+  pub fn get(&self, pointer: Pointer) -> Self {
+    self.get_at(pointer, self)
+  }
+
+  /// Recursive worker behind `get`. `root` is the definition's root schema,
+  /// threaded down so a `Reference` can be resolved no matter how deeply
+  /// nested it is found—`Reference`s are always relative to the root, never
+  /// to whatever schema happens to contain them.
+  fn get_at(&self, mut pointer: Pointer, root: &Schema) -> Self {
     if pointer.len() == 0 {
       self.clone()
     } else {
@@ -105,27 +147,91 @@ impl Schema {
         &Schema::String{..} => Schema::None,
         &Schema::Array{ref items} => {
           if INTEGER_RE.is_match(&pointer.remove(0)) {
-            items.get(pointer)
+            items.get_at(pointer, root)
           } else {
             Schema::None
           }
         },
         &Schema::Object{ref properties,..} => {
           if let Some(schema) = properties.get(&pointer.remove(0)) {
-            schema.get(pointer)
+            schema.get_at(pointer, root)
           } else {
             Schema::None
           }
         },
-        &Schema::Enum(_) => Schema::None
+        &Schema::Enum(_) => Schema::None,
+        &Schema::OneOf{ref discriminator, ref variants} => {
+          let key = pointer.remove(0);
+
+          if key == *discriminator {
+            // The discriminator itself is always one of the variant tags.
+            Schema::Enum(variants.keys().cloned().collect())
+          } else {
+            // Without a concrete value we don’t know which branch applies, so
+            // we can only answer if every branch agrees on the schema at this
+            // pointer. Otherwise there’s no single schema to report.
+            let mut nested_pointer = vec![key];
+            nested_pointer.extend(pointer);
+            let mut schemas = variants.values().map(|schema| schema.get_at(nested_pointer.clone(), root));
+
+            match schemas.next() {
+              Some(first) => if schemas.all(|schema| schema == first) { first } else { Schema::None },
+              None => Schema::None
+            }
+          }
+        },
+        &Schema::AnyOf(ref schemas) | &Schema::AllOf(ref schemas) => {
+          // Without a concrete value to test against each schema, we can only
+          // answer if every schema agrees on what's at this pointer.
+          let mut results = schemas.iter().map(|schema| schema.get_at(pointer.clone(), root));
+
+          match results.next() {
+            Some(first) => if results.all(|schema| schema == first) { first } else { Schema::None },
+            None => Schema::None
+          }
+        },
+        // Resolve the reference against the root first, then keep walking
+        // the remaining pointer into whatever it points to.
+        &Schema::Reference(ref target) => root.get_at(target.clone(), root).get_at(pointer, root),
+        // A negation only says what a value *isn't*; there's no positive
+        // shape here to answer a nested pointer with.
+        &Schema::Not(_) => Schema::None
       }
     }
   }
-  
+
+  /// Selects the single concrete schema variant whose discriminator value
+  /// matches the value found at the discriminator property of `value`. Only
+  /// meaningful for `Schema::OneOf`; every other variant—including
+  /// `Reference`, which would need a root to resolve against a value
+  /// borrowed no longer than `self`—simply resolves to itself.
+  pub fn resolve(&self, value: &Value) -> &Schema {
+    static NONE: Schema = Schema::None;
+
+    match *self {
+      Schema::OneOf{ref discriminator, ref variants} => {
+        let tag = match *value {
+          Value::Object(ref object) => object.get(discriminator),
+          _ => None
+        };
+
+        tag.and_then(|tag| variants.get(tag)).unwrap_or(&NONE)
+      },
+      ref schema => schema
+    }
+  }
+
   /// Validates a query that a user would like to make on the database by
   /// comparing it to the schema. Mostly checks that all properties described
   /// in the query are accessible according to the schema.
   pub fn validate_query(&self, query: &Query) -> Result<(), Error> {
+    self.validate_query_at(query, self)
+  }
+
+  /// Recursive worker behind `validate_query`. `root` is the definition's
+  /// root schema, threaded down the same way `get_at` threads it, so a
+  /// `Reference` resolves no matter how deeply nested it is found.
+  fn validate_query_at(&self, query: &Query, root: &Schema) -> Result<(), Error> {
     static NO_PRIMITIVE_HINT: &'static str = "Try not querying specific properties of a primitive like `null` or `boolean`.";
     match (self, query) {
       // No schema describes these values, its the wild west. Go crazy query.
@@ -147,7 +253,7 @@ impl Schema {
             if !INTEGER_RE.is_match(key) {
               Err(Error::validation(format!("Cannot query non-integer \"{}\" array property.", key), "Only query integer array keys like 1, 2, and 3."))
             } else {
-              items.validate_query(&query_properties.get(selection).unwrap())
+              items.validate_query_at(&query_properties.get(selection).unwrap(), root)
             }
           }
         }).find(|r| r.is_err()) {
@@ -160,7 +266,7 @@ impl Schema {
         match query_properties.keys().map(|selection| match selection {
           &Selection::Key(ref key) => {
             if let Some(property_schema) = properties.get(key) {
-              property_schema.validate_query(&query_properties.get(selection).unwrap())
+              property_schema.validate_query_at(&query_properties.get(selection).unwrap(), root)
             } else if !additional_properties {
               Err(Error::validation(format!("Cannot query object property \"{}\".", key), "Query an object property that is defined in the schema."))
             } else {
@@ -173,9 +279,289 @@ impl Schema {
         }
       },
       (&Schema::Enum(_), &Query::Value) => Ok(()),
-      (&Schema::Enum(_), &Query::Object(_)) => Err(Error::validation("Cannot deeply query an enum.", NO_PRIMITIVE_HINT))
+      (&Schema::Enum(_), &Query::Object(_)) => Err(Error::validation("Cannot deeply query an enum.", NO_PRIMITIVE_HINT)),
+      // A query is valid for a `OneOf` as long as it is valid against at
+      // least one of the variants, the union of variant properties.
+      (&Schema::OneOf{ref variants,..}, _) => {
+        match variants.values().map(|schema| schema.validate_query_at(query, root)).find(|result| result.is_ok()) {
+          Some(result) => result,
+          None => Err(Error::validation(
+            "Query does not match any of the possible variants.",
+            "Query a property common to all variants, or the discriminator."
+          ))
+        }
+      },
+      // A query is valid for an `AnyOf` as long as it is valid against at
+      // least one of the schemas.
+      (&Schema::AnyOf(ref schemas), _) => {
+        match schemas.iter().map(|schema| schema.validate_query_at(query, root)).find(|result| result.is_ok()) {
+          Some(result) => result,
+          None => Err(Error::validation(
+            "Query does not match any of the possible schemas.",
+            "Query a property common to at least one of the schemas."
+          ))
+        }
+      },
+      // A query is valid for an `AllOf` only if it is valid against every
+      // one of the schemas.
+      (&Schema::AllOf(ref schemas), _) => {
+        match schemas.iter().map(|schema| schema.validate_query_at(query, root)).find(|result| result.is_err()) {
+          None => Ok(()),
+          Some(error) => error
+        }
+      },
+      // Resolve the reference against the root and validate the query
+      // against whatever it points to.
+      (&Schema::Reference(ref target), _) => root.get(target.clone()).validate_query_at(query, root),
+      // A negation only rules values out; it says nothing about which
+      // properties are queryable, so any query shape is let through.
+      (&Schema::Not(_), _) => Ok(())
     }
   }
+
+  /// Validates concrete data returned from (or about to be written to) a
+  /// driver against the schema. Unlike `validate_query`, which only checks
+  /// that the *shape* of a query is accessible, this walks the schema and the
+  /// value in lock-step and enforces every constraint the schema declares.
+  ///
+  /// Every violation found anywhere in the value is collected instead of
+  /// bailing out at the first one, so fixing a large document doesn’t require
+  /// validating it over and over again one error at a time.
+  pub fn validate_value(&self, value: &Value) -> Result<(), Error> {
+    let mut errors = ValidationErrors::new();
+    self.validate_value_at(value, &Vec::new(), self, &mut errors);
+    errors.into_result()
+  }
+
+  /// Like `validate_value`, but returns every violation individually instead
+  /// of folding them into one combined `Error`, each tagged with the
+  /// JSON-pointer-style path (e.g. `/goodbye/moon`) where it occurred. Lets
+  /// an API surface report "fields X, Y, and Z are all invalid" in one
+  /// response rather than one field at a time.
+  pub fn validate_value_violations(&self, value: &Value) -> Result<(), Vec<Error>> {
+    let mut errors = ValidationErrors::new();
+    self.validate_value_at(value, &Vec::new(), self, &mut errors);
+    errors.into_violations()
+  }
+
+  /// Recursive worker behind `validate_value`. `path` is the pointer to
+  /// `value` from the root of the document, threaded down so nested
+  /// violations are recorded against their full pointer; `root` is the
+  /// definition's root schema, threaded the same way `get_at` threads it, so
+  /// a `Reference` resolves no matter how deeply nested it is found.
+  fn validate_value_at(&self, value: &Value, path: &Pointer, root: &Schema, errors: &mut ValidationErrors) {
+    static WRONG_TYPE_HINT: &'static str = "Make sure the value you’re validating matches the type declared by the schema.";
+
+    match (self, value) {
+      // `Schema::None` does not validate anything, so any value is allowed.
+      (&Schema::None, _) => {},
+      (&Schema::Null, &Value::Null(_)) => {},
+      (&Schema::Null, _) => errors.push(path, Error::validation("Expected null.", WRONG_TYPE_HINT)),
+      (&Schema::Boolean, &Value::Boolean(_)) => {},
+      (&Schema::Boolean, _) => errors.push(path, Error::validation("Expected a boolean.", WRONG_TYPE_HINT)),
+      (&Schema::Number{ multiple_of, minimum, exclusive_minimum, maximum, exclusive_maximum }, _) => {
+        let number = match *value {
+          Value::I64(number) => number as f64,
+          Value::U64(number) => number as f64,
+          Value::F64(number) => number,
+          _ => return errors.push(path, Error::validation("Expected a number.", WRONG_TYPE_HINT))
+        };
+
+        if let Some(minimum) = minimum {
+          if number < minimum || (exclusive_minimum && number == minimum) {
+            errors.push(path, Error::validation(
+              format!("Number {} is less than the minimum of {}.", number, minimum),
+              format!("Use a number greater than{} {}.", if exclusive_minimum { "" } else { " or equal to" }, minimum)
+            ));
+          }
+        }
+
+        if let Some(maximum) = maximum {
+          if number > maximum || (exclusive_maximum && number == maximum) {
+            errors.push(path, Error::validation(
+              format!("Number {} is greater than the maximum of {}.", number, maximum),
+              format!("Use a number less than{} {}.", if exclusive_maximum { "" } else { " or equal to" }, maximum)
+            ));
+          }
+        }
+
+        if let Some(multiple_of) = multiple_of {
+          if number % f64::from(multiple_of) != 0.0 {
+            errors.push(path, Error::validation(
+              format!("Number {} is not a multiple of {}.", number, multiple_of),
+              format!("Use a number that is a multiple of {}.", multiple_of)
+            ));
+          }
+        }
+      },
+      (&Schema::String{ min_length, max_length, ref pattern }, &Value::String(ref string)) => {
+        let length = string.chars().count() as u64;
+
+        if let Some(min_length) = min_length {
+          if length < min_length {
+            errors.push(path, Error::validation(
+              format!("String \"{}\" is shorter than the minimum length of {}.", string, min_length),
+              format!("Use a string at least {} characters long.", min_length)
+            ));
+          }
+        }
+
+        if let Some(max_length) = max_length {
+          if length > max_length {
+            errors.push(path, Error::validation(
+              format!("String \"{}\" is longer than the maximum length of {}.", string, max_length),
+              format!("Use a string at most {} characters long.", max_length)
+            ));
+          }
+        }
+
+        if let Some(ref pattern) = *pattern {
+          if !pattern.is_match(string) {
+            errors.push(path, Error::validation(
+              format!("String \"{}\" does not match the pattern /{}/.", string, pattern),
+              "Use a string which matches the required pattern."
+            ));
+          }
+        }
+      },
+      (&Schema::String{..}, _) => errors.push(path, Error::validation("Expected a string.", WRONG_TYPE_HINT)),
+      (&Schema::Array{ ref items }, &Value::Array(ref array)) => {
+        for (index, item) in array.iter().enumerate() {
+          items.validate_value_at(item, &push(path, index.to_string()), root, errors);
+        }
+      },
+      (&Schema::Array{..}, _) => errors.push(path, Error::validation("Expected an array.", WRONG_TYPE_HINT)),
+      (&Schema::Object{ ref properties, ref required, additional_properties }, &Value::Object(ref object)) => {
+        for key in required {
+          if object.get(key).is_none() {
+            errors.push(&push(path, key.clone()), Error::validation(
+              format!("Missing required property \"{}\".", key),
+              format!("Add the \"{}\" property to the object.", key)
+            ));
+          }
+        }
+
+        for (key, property_value) in object.clone() {
+          if let Some(property_schema) = properties.get(&key) {
+            property_schema.validate_value_at(&property_value, &push(path, key), root, errors);
+          } else if !additional_properties {
+            errors.push(&push(path, key.clone()), Error::validation(
+              format!("Unknown object property \"{}\".", key),
+              "Remove the property or add it to the schema."
+            ));
+          }
+        }
+      },
+      (&Schema::Object{..}, _) => errors.push(path, Error::validation("Expected an object.", WRONG_TYPE_HINT)),
+      (&Schema::Enum(ref values), _) => {
+        if !values.contains(value) {
+          errors.push(path, Error::validation("Value did not match any of the schema’s enum variants.", "Use one of the values permitted by the enum."));
+        }
+      },
+      // A `OneOf` is valid if the value matches the variant named by the
+      // discriminator; an unrecognized (or missing) discriminator tag is
+      // itself a violation, since there's no variant left to validate against.
+      (&Schema::OneOf{ref discriminator, ref variants}, _) => {
+        let tag = match *value {
+          Value::Object(ref object) => object.get(discriminator),
+          _ => None
+        };
+
+        match tag.and_then(|tag| variants.get(tag)) {
+          Some(variant_schema) => variant_schema.validate_value_at(value, path, root, errors),
+          None => errors.push(path, Error::validation(
+            "Value did not match any of the possible variants.",
+            format!("Set \"{}\" to one of the variant tags.", discriminator)
+          ))
+        }
+      },
+      // Unlike `AllOf` below, a single branch match is all `AnyOf` needs, so
+      // there's no single violation to blame—just check each branch into its
+      // own scratch accumulator rather than threading `errors` through.
+      (&Schema::AnyOf(ref schemas), _) => {
+        if !schemas.iter().any(|schema| {
+          let mut branch_errors = ValidationErrors::new();
+          schema.validate_value_at(value, path, root, &mut branch_errors);
+          branch_errors.0.is_empty()
+        }) {
+          errors.push(path, Error::validation(
+            "Value did not match any of the possible schemas.",
+            "Make the value match at least one of the listed schemas."
+          ));
+        }
+      },
+      (&Schema::AllOf(ref schemas), _) => {
+        for schema in schemas {
+          schema.validate_value_at(value, path, root, errors);
+        }
+      },
+      // Resolve the reference against the root and validate the value
+      // against whatever it points to.
+      (&Schema::Reference(ref target), _) => root.get(target.clone()).validate_value_at(value, path, root, errors),
+      (&Schema::Not(ref schema), _) => {
+        let mut negated_errors = ValidationErrors::new();
+        schema.validate_value_at(value, path, root, &mut negated_errors);
+        if negated_errors.0.is_empty() {
+          errors.push(path, Error::validation(
+            "Value matched a schema it must not match.",
+            "Use a value that does not satisfy the negated schema."
+          ));
+        }
+      }
+    }
+  }
+}
+
+/// Returns a new pointer equal to `path` with `key` appended, leaving `path`
+/// itself untouched. A small helper to keep the recursive `validate_value_at`
+/// calls above readable.
+fn push(path: &Pointer, key: Key) -> Pointer {
+  let mut path = path.clone();
+  path.push(key);
+  path
+}
+
+/// Accumulates `(Pointer, Error)` pairs produced while recursively validating
+/// a value against a schema. Converting the accumulator into a `Result`
+/// collapses an empty accumulator into `Ok(())` and a non-empty one into a
+/// single `Error` listing every offending property path, mirroring the
+/// multi-error parameter-verification pattern used elsewhere in HTTP APIs.
+#[derive(Default)]
+struct ValidationErrors(Vec<(Pointer, Error)>);
+
+impl ValidationErrors {
+  fn new() -> Self {
+    ValidationErrors(Vec::new())
+  }
+
+  fn push(&mut self, path: &Pointer, error: Error) {
+    self.0.push((path.clone(), error));
+  }
+
+  fn into_result(self) -> Result<(), Error> {
+    if self.0.is_empty() {
+      return Ok(());
+    }
+
+    let message = self.0.iter().fold(String::from("parameter verification failed:"), |message, &(ref path, ref error)| {
+      format!("{}\n- \"/{}\": {}", message, path.join("/"), error.message())
+    });
+
+    Err(Error::validation(message, "Fix each of the listed properties and try again."))
+  }
+
+  /// Like `into_result`, but keeps every violation separate instead of
+  /// folding them into one combined message, each tagged with the
+  /// JSON-pointer-style path (e.g. `/goodbye/moon`) where it occurred.
+  fn into_violations(self) -> Result<(), Vec<Error>> {
+    if self.0.is_empty() {
+      return Ok(());
+    }
+
+    Err(self.0.into_iter().map(|(path, error)| {
+      Error::new(error.code(), format!("/{}: {}", path.join("/"), error.message()), error.hint())
+    }).collect())
+  }
 }
 
 #[cfg(test)]
@@ -366,4 +752,127 @@ mod tests {
       Selection::Key("moon".to_string()) => Query::Value
     })).is_ok());
   }
+
+  fn one_of_shape() -> Schema {
+    Schema::OneOf {
+      discriminator: String::from("kind"),
+      variants: linear_map! {
+        vstring!("dog") => Schema::Object {
+          required: vec![],
+          additional_properties: false,
+          properties: linear_map! {
+            String::from("kind") => Schema::String { min_length: None, max_length: None, pattern: None },
+            String::from("bark_volume") => Schema::Number {
+              multiple_of: None, minimum: None, exclusive_minimum: false, maximum: None, exclusive_maximum: false
+            }
+          }
+        },
+        vstring!("cat") => Schema::Object {
+          required: vec![],
+          additional_properties: false,
+          properties: linear_map! {
+            String::from("kind") => Schema::String { min_length: None, max_length: None, pattern: None },
+            String::from("lives_left") => Schema::Number {
+              multiple_of: None, minimum: None, exclusive_minimum: false, maximum: None, exclusive_maximum: false
+            }
+          }
+        }
+      }
+    }
+  }
+
+  #[test]
+  fn test_get_one_of() {
+    let schema = one_of_shape();
+    // Asking for the discriminator itself returns the set of possible tags,
+    // not the schema the variants declare for `kind`.
+    assert_eq!(schema.get(point!["kind"]), Schema::Enum(vec![vstring!("dog"), vstring!("cat")]));
+    // No variant agrees on `bark_volume` vs `lives_left`, so there is no
+    // single schema to report.
+    assert_eq!(schema.get(point!["bark_volume"]), Schema::None);
+  }
+
+  #[test]
+  fn test_resolve_one_of() {
+    let schema = one_of_shape();
+    let dog = vobject!{"kind" => vstring!("dog"), "bark_volume" => vi64!(11)};
+    let cat = vobject!{"kind" => vstring!("cat"), "lives_left" => vi64!(9)};
+
+    assert_eq!(schema.resolve(&dog), &Schema::Object {
+      required: vec![],
+      additional_properties: false,
+      properties: linear_map! {
+        String::from("kind") => Schema::String { min_length: None, max_length: None, pattern: None },
+        String::from("bark_volume") => Schema::Number {
+          multiple_of: None, minimum: None, exclusive_minimum: false, maximum: None, exclusive_maximum: false
+        }
+      }
+    });
+    assert!(schema.resolve(&cat) != schema.resolve(&dog));
+    // Only `OneOf` discriminates; every other variant resolves to itself.
+    assert_eq!(Schema::Boolean.resolve(&vbool!(true)), &Schema::Boolean);
+  }
+
+  #[test]
+  fn test_query_one_of() {
+    let schema = one_of_shape();
+    assert!(schema.validate_query(&Query::Object(linear_map! {
+      Selection::Key("kind".to_string()) => Query::Value
+    })).is_ok());
+    assert!(schema.validate_query(&Query::Object(linear_map! {
+      Selection::Key("bark_volume".to_string()) => Query::Value
+    })).is_ok());
+    schema.validate_query(&Query::Object(linear_map! {
+      Selection::Key("paws".to_string()) => Query::Value
+    })).unwrap_err().assert_message("does not match any of the possible variants");
+  }
+
+  /// An object whose `self` property is a `Reference` back to its own root,
+  /// directly cyclic. Building this as a plain `Schema::Object` containing
+  /// itself would recurse forever; `Reference` stores only a pointer, so the
+  /// cycle only gets walked as deep as a concrete pointer or query goes.
+  fn cyclic_shape() -> Schema {
+    Schema::Object {
+      required: vec![],
+      additional_properties: false,
+      properties: linear_map! {
+        String::from("name") => Schema::String { min_length: None, max_length: None, pattern: None },
+        String::from("self") => Schema::Reference(point![])
+      }
+    }
+  }
+
+  #[test]
+  fn test_get_reference() {
+    let schema = cyclic_shape();
+    assert_eq!(schema.get(point!["self", "name"]), Schema::String { min_length: None, max_length: None, pattern: None });
+    assert_eq!(schema.get(point!["self", "self", "name"]), Schema::String { min_length: None, max_length: None, pattern: None });
+    assert_eq!(schema.get(point!["self", "nope"]), Schema::None);
+  }
+
+  #[test]
+  fn test_query_reference() {
+    let schema = cyclic_shape();
+    assert!(schema.validate_query(&Query::Object(linear_map! {
+      Selection::Key("self".to_string()) => Query::Object(linear_map! {
+        Selection::Key("self".to_string()) => Query::Object(linear_map! {
+          Selection::Key("name".to_string()) => Query::Value
+        })
+      })
+    })).is_ok());
+    schema.validate_query(&Query::Object(linear_map! {
+      Selection::Key("self".to_string()) => Query::Object(linear_map! {
+        Selection::Key("nope".to_string()) => Query::Value
+      })
+    })).unwrap_err().assert_message("Cannot query object property \"nope\"");
+  }
+
+  #[test]
+  fn test_query_not_allows_any_shape() {
+    let schema = Schema::Not(Box::new(Schema::Boolean));
+    assert!(schema.validate_query(&Query::Value).is_ok());
+    assert!(schema.validate_query(&Query::Object(linear_map! {
+      Selection::Key("anything".to_string()) => Query::Value
+    })).is_ok());
+  }
 }
@@ -1,85 +1,266 @@
+//! Deserializes an Ardite Schema Definition file into a `Definition`, picking
+//! the `serde` frontend to parse it with from its file extension (`.json`,
+//! `.yml`/`.yaml`, `.json5`, `.toml`, `.msgpack`/`.mpk`), plus Avro schema
+//! documents (`.avsc`/`.avro`) for users migrating existing Avro type
+//! definitions. Besides the primitive `Schema` variants, the intermediary
+//! `SerdeSchema` supports `anyOf`/`allOf`/`not` composition and a `$ref`
+//! pointing at a named schema declared under the document's top-level
+//! `definitions` map—deliberately not `oneOf`, since `Schema` already has a
+//! discriminated `OneOf` variant for tagged unions (see its doc comment).
+//!
+//! `parse_file`/`parse_reader_with_format` expose the `SerdeDefinition`/
+//! `SerdeSchema` intermediary directly, for callers like `codegen`/`graphql`
+//! which need to introspect the constraints a file declared—the live `Schema`
+//! has no getters to read them back out of once lowered.
+
 use std::collections::BTreeMap;
-use std::io::BufReader;
+use std::io::{BufReader, Read, Write};
 use std::path::PathBuf;
 use std::fs::File;
 use linear_map::LinearMap;
+use json5;
+use regex::Regex;
+use rmp_serde;
+use serde::{Serialize, Deserialize};
 use serde_json;
+use serde_yaml;
+use toml;
 use error::{Error, ErrorCode};
 use definition::Definition;
 use definition::schema::Schema;
 use value::Value;
 
+/// Which wire format a `SerdeDefinition` is encoded in. Lets callers pick a
+/// format explicitly via `parse_reader_with_format`/`to_writer` instead of
+/// inferring one from a file extension, which matters for payloads that have
+/// no file extension to read.
+pub enum Format {
+  /// Plain JSON, via `serde_json`.
+  Json,
+  /// YAML, via `serde_yaml`.
+  Yaml,
+  /// Compact binary MessagePack, via `rmp_serde`. Meant for efficient
+  /// transport between an Ardite service and remote drivers where
+  /// per-request JSON parsing is too costly.
+  MsgPack
+}
+
+/// Reads `path` and deserializes it into a `Definition`, picking the `serde`
+/// frontend to parse it with from its file extension. Every error returned
+/// is tagged with `path` (see `Error::at_file`).
 pub fn from_file(path: PathBuf) -> Result<Definition, Error> {
+  parse_file(path.clone())
+    .and_then(SerdeDefinition::to_definition)
+    .map_err(|error| error.at_file(&path))
+}
+
+/// Reads `path` and deserializes it into the `SerdeDefinition` intermediary,
+/// without lowering it into a `Definition`. Picks the frontend to parse it
+/// with from its file extension, the same as `from_file`.
+pub fn parse_file(path: PathBuf) -> Result<SerdeDefinition, Error> {
   let extension = path.extension().map_or("", |s| s.to_str().unwrap());
   let file = try!(File::open(&path));
   let reader = BufReader::new(file);
+
   match extension {
-    "json" => {
-      let data: SerdeDefinition = try!(serde_json::from_reader(reader));
-      Ok(try!(data.to_definition()))
+    "json" => Ok(try!(serde_json::from_reader(reader))),
+    "yml" | "yaml" => Ok(try!(serde_yaml::from_reader(reader))),
+    "json5" => {
+      let mut reader = reader;
+      let mut contents = String::new();
+      try!(reader.read_to_string(&mut contents));
+      Ok(try!(json5::from_str(&contents)))
     },
-    "yml" => Err(Error::unimplemented("YAML file parsing has not yet been implemented.")),
+    "toml" => {
+      let mut reader = reader;
+      let mut contents = String::new();
+      try!(reader.read_to_string(&mut contents));
+      Ok(try!(toml::from_str(&contents)))
+    },
+    "avsc" | "avro" => avro_schema_into_serde_definition(try!(serde_json::from_reader(reader))),
+    "msgpack" | "mpk" => parse_reader_with_format(reader, Format::MsgPack),
     _ => Err(Error {
       code: ErrorCode::NotAcceptable,
       message: String::from(format!("File extension '{}' cannot be deserialized in '{}'.", extension, path.display())),
-      hint: Some(String::from(format!("Use a recognizable file extension like '.json' or '.yml'.")))
+      hint: Some(String::from(format!(
+        "Use a recognizable file extension like '.json', '.yml'/'.yaml', '.json5', '.toml', '.avsc'/'.avro', or '.msgpack'/'.mpk'."
+      )))
     })
   }
 }
 
-/// Type used to deserialize data files into a usable definition type.
-#[derive(Deserialize)]
-struct SerdeDefinition {
-  data: SerdeSchema
+/// Reads a `SerdeDefinition` out of `reader`, decoded in the given `format`
+/// rather than inferred from a file extension. Pair with `to_writer` to
+/// round-trip a definition through a specific wire format.
+pub fn parse_reader_with_format<R: Read>(reader: R, format: Format) -> Result<SerdeDefinition, Error> {
+  match format {
+    Format::Json => Ok(try!(serde_json::from_reader(reader))),
+    Format::Yaml => Ok(try!(serde_yaml::from_reader(reader))),
+    Format::MsgPack => {
+      let mut deserializer = rmp_serde::Deserializer::new(reader);
+      Ok(try!(SerdeDefinition::deserialize(&mut deserializer)))
+    }
+  }
+}
+
+/// Writes `definition` out to `path`, picking the frontend to serialize it
+/// with from its file extension—the inverse of `from_file`/`parse_file` at
+/// the wire-format level.
+pub fn to_file(definition: &SerdeDefinition, path: PathBuf) -> Result<(), Error> {
+  let extension = path.extension().map_or("", |s| s.to_str().unwrap());
+  let mut file = try!(File::create(&path));
+
+  let string = match extension {
+    "json" => try!(serde_json::to_string_pretty(definition)),
+    "yml" | "yaml" => try!(serde_yaml::to_string(definition)),
+    "toml" => try!(toml::to_string_pretty(definition)),
+    _ => return Err(Error::new(
+      ErrorCode::NotAcceptable,
+      format!("File extension '{}' cannot be serialized in '{}'.", extension, path.display()),
+      Some("Use a recognizable file extension like '.json', '.yml'/'.yaml', or '.toml'.".to_owned())
+    ))
+  };
+
+  Ok(try!(file.write_all(string.as_bytes())))
+}
+
+/// Writes `definition` to `writer`, encoded in the given `format`.
+pub fn to_writer<W: Write>(definition: &SerdeDefinition, writer: &mut W, format: Format) -> Result<(), Error> {
+  match format {
+    Format::Json => try!(serde_json::to_writer(writer, definition)),
+    Format::Yaml => try!(serde_yaml::to_writer(writer, definition)),
+    Format::MsgPack => {
+      let mut serializer = rmp_serde::Serializer::new(writer);
+      try!(definition.serialize(&mut serializer))
+    }
+  }
+  Ok(())
+}
+
+/// Type used to deserialize data files into a usable definition type. `pub`
+/// so callers like `codegen`/`graphql` can walk the raw tree a file declared
+/// directly, since the live `Schema` has no getters to read it back out of
+/// once lowered.
+#[derive(Serialize, Deserialize)]
+pub struct SerdeDefinition {
+  pub data: SerdeSchema,
+  /// Named schemas a `$ref` like `"#/definitions/foo"` may point to.
+  #[serde(default)]
+  pub definitions: BTreeMap<String, SerdeSchema>
 }
 
 impl SerdeDefinition {
   /// Transforms the intermediary type into the useful type.
-  fn to_definition(self) -> Result<Definition, Error> {
+  pub fn to_definition(self) -> Result<Definition, Error> {
     Ok(Definition {
-      data: try!(self.data.to_schema())
+      data: try!(self.data.to_schema(&self.definitions, &mut Vec::new()))
     })
   }
 }
 
 /// Intermediary type used to deserialized data files into a usable schema
-/// enum.
-#[derive(Deserialize)]
-struct SerdeSchema {
+/// enum. `pub`, for the same reason as `SerdeDefinition`.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct SerdeSchema {
   #[serde(rename="type")]
-  type_: Option<String>,
+  pub type_: Option<String>,
   #[serde(rename="multipleOf")]
-  multiple_of: Option<f32>,
-  minimum: Option<f64>,
+  pub multiple_of: Option<f32>,
+  pub minimum: Option<f64>,
   #[serde(rename="exclusiveMinimum")]
-  exclusive_minimum: Option<bool>,
-  maximum: Option<f64>,
+  pub exclusive_minimum: Option<bool>,
+  pub maximum: Option<f64>,
   #[serde(rename="exclusiveMaximum")]
-  exclusive_maximum: Option<bool>,
+  pub exclusive_maximum: Option<bool>,
   #[serde(rename="minLength")]
-  min_length: Option<u64>,
+  pub min_length: Option<u64>,
   #[serde(rename="maxLength")]
-  max_length: Option<u64>,
-  pattern: Option<String>,
-  items: Option<Box<SerdeSchema>>,
-  properties: Option<BTreeMap<String, SerdeSchema>>,
-  required: Option<Vec<String>>,
+  pub max_length: Option<u64>,
+  pub pattern: Option<String>,
+  pub items: Option<Box<SerdeSchema>>,
+  pub properties: Option<BTreeMap<String, SerdeSchema>>,
+  pub required: Option<Vec<String>>,
   #[serde(rename="additionalProperties")]
-  additional_properties: Option<bool>,
-  // TODO: `enum` should not just accept strings.
+  pub additional_properties: Option<bool>,
   #[serde(rename="enum")]
-  enum_: Option<Vec<String>>
+  pub enum_: Option<Vec<Value>>,
+  // There's deliberately no `oneOf` here: `Schema` already has a `OneOf`
+  // variant for tagged unions, discriminated by a named property so it stays
+  // searchable (see its doc comment). An unconstrained `oneOf` would defeat
+  // that, so it isn't exposed through this format.
+  #[serde(rename="anyOf")]
+  pub any_of: Option<Vec<SerdeSchema>>,
+  #[serde(rename="allOf")]
+  pub all_of: Option<Vec<SerdeSchema>>,
+  pub not: Option<Box<SerdeSchema>>,
+  #[serde(rename="$ref")]
+  pub ref_: Option<String>
 }
 
+/// The JSON-pointer-style prefix a `$ref` must use to point at a definition
+/// declared in the same document's top-level `definitions` map.
+static REF_PREFIX: &'static str = "#/definitions/";
+
 impl SerdeSchema {
-  /// Transforms the intermediary type into the useful type.
-  fn to_schema(self) -> Result<Schema, Error> {
+  /// Transforms the intermediary type into the useful type, resolving `$ref`
+  /// against `definitions` and rejecting a `$ref` cycle. `resolving` tracks
+  /// the names currently being resolved, so a definition that (directly or
+  /// transitively) refers back to itself is caught instead of recursing
+  /// forever.
+  fn to_schema(self, definitions: &BTreeMap<String, SerdeSchema>, resolving: &mut Vec<String>) -> Result<Schema, Error> {
+    if let Some(ref_path) = self.ref_ {
+      if !ref_path.starts_with(REF_PREFIX) {
+        return Err(Error::validation(
+          format!("Unsupported `$ref` '{}'.", ref_path),
+          format!("Reference a definition like \"{}name\".", REF_PREFIX)
+        ));
+      }
+
+      let name = ref_path[REF_PREFIX.len()..].to_owned();
+
+      if resolving.contains(&name) {
+        return Err(Error::validation(
+          format!("Cyclic `$ref` detected resolving \"{}\".", name),
+          "Remove the cycle between these definitions."
+        ));
+      }
+
+      return match definitions.get(&name) {
+        Some(schema) => {
+          resolving.push(name);
+          let result = schema.clone().to_schema(definitions, resolving);
+          resolving.pop();
+          result
+        },
+        None => Err(Error::validation(
+          format!("No definition named \"{}\" was found.", name),
+          format!("Define it under `definitions`, or fix the `$ref`.")
+        ))
+      };
+    }
+
+    if let Some(any_of) = self.any_of {
+      return Ok(Schema::AnyOf(try!(
+        any_of.into_iter().map(|schema| schema.to_schema(definitions, resolving)).collect::<Result<Vec<Schema>, Error>>()
+      )));
+    }
+
+    if let Some(all_of) = self.all_of {
+      return Ok(Schema::AllOf(try!(
+        all_of.into_iter().map(|schema| schema.to_schema(definitions, resolving)).collect::<Result<Vec<Schema>, Error>>()
+      )));
+    }
+
+    if let Some(not) = self.not {
+      return Ok(Schema::Not(Box::new(try!(not.to_schema(definitions, resolving)))));
+    }
+
     match self.type_ {
       Some(type_) => match type_.as_ref() {
         "null" => Ok(Schema::Null),
         "boolean" => Ok(Schema::Boolean),
         "number" | "integer" => Ok(Schema::Number {
-          multiple_of: self.multiple_of,
+          multiple_of: if type_ == "integer" { Some(1.0) } else { self.multiple_of },
           minimum: self.minimum,
           exclusive_minimum: self.exclusive_minimum.unwrap_or(false),
           maximum: self.maximum,
@@ -88,12 +269,18 @@ impl SerdeSchema {
         "string" => Ok(Schema::String {
           min_length: self.min_length,
           max_length: self.max_length,
-          pattern: self.pattern
+          pattern: match self.pattern {
+            Some(pattern) => Some(try!(Regex::new(&pattern).map_err(|error| Error::validation(
+              format!("Invalid `pattern` regular expression '{}': {}", pattern, error),
+              "Fix the regular expression syntax."
+            )))),
+            None => None
+          }
         }),
         "array" => {
           if let Some(items) = self.items {
             Ok(Schema::Array {
-              items: Box::new(try!(items.to_schema()))
+              items: Box::new(try!(items.to_schema(definitions, resolving)))
             })
           } else {
             Err(Error::validation("Missing `items` property for type 'array'.", "Add a schema at `items`."))
@@ -105,7 +292,7 @@ impl SerdeSchema {
           properties: {
             let mut map = LinearMap::new();
             for (key, definition) in self.properties.unwrap_or(BTreeMap::new()) {
-              map.insert(key, try!(definition.to_schema()));
+              map.insert(key, try!(definition.to_schema(definitions, resolving)));
             }
             map
           }
@@ -114,7 +301,7 @@ impl SerdeSchema {
       },
       None => {
         if let Some(enum_) = self.enum_ {
-          Ok(Schema::Enum(enum_.into_iter().map(|s| Value::String(s)).collect()))
+          Ok(Schema::Enum(enum_))
         } else {
           Err(Error::validation("No schema type specified.", "Set a `type` property or an `enum` property."))
         }
@@ -123,13 +310,166 @@ impl SerdeSchema {
   }
 }
 
+/// Transforms a top-level Avro schema document into a `SerdeDefinition`.
+/// Avro files describe a single named type (almost always a `record`), so
+/// that type becomes the only property of the definition's root object.
+fn avro_schema_into_serde_definition(avro_schema: serde_json::Value) -> Result<SerdeDefinition, Error> {
+  let name = avro_schema.get("name")
+    .and_then(|name| name.as_str())
+    .map_or_else(|| "default".to_owned(), |name| name.to_owned());
+
+  let mut properties = BTreeMap::new();
+  properties.insert(name.clone(), try!(avro_type_into_serde_schema(&avro_schema)));
+
+  Ok(SerdeDefinition {
+    data: SerdeSchema { properties: Some(properties), required: Some(vec![name]), ..empty_serde_schema(Some("object")) },
+    definitions: BTreeMap::new()
+  })
+}
+
+/// A `SerdeSchema` with every field empty except `type_`, for building one up
+/// piece by piece without repeating every field name. Pass `None` for a
+/// schema recognized by its `enum_` field instead of a `type`.
+fn empty_serde_schema(type_: Option<&str>) -> SerdeSchema {
+  SerdeSchema {
+    type_: type_.map(str::to_owned),
+    multiple_of: None,
+    minimum: None,
+    exclusive_minimum: None,
+    maximum: None,
+    exclusive_maximum: None,
+    min_length: None,
+    max_length: None,
+    pattern: None,
+    items: None,
+    properties: None,
+    required: None,
+    additional_properties: None,
+    enum_: None,
+    any_of: None,
+    all_of: None,
+    not: None,
+    ref_: None
+  }
+}
+
+/// Transforms an Avro type—a primitive name, a complex type object, or a
+/// union array—into the equivalent `SerdeSchema`. Bare unions (outside of a
+/// record field) are lowered to their first non-`"null"` branch.
+fn avro_type_into_serde_schema(avro_type: &serde_json::Value) -> Result<SerdeSchema, Error> {
+  match *avro_type {
+    serde_json::Value::String(ref name) => avro_primitive_into_serde_schema(name),
+    serde_json::Value::Object(_) => avro_complex_into_serde_schema(avro_type),
+    serde_json::Value::Array(ref union) => match union.iter().find(|branch| branch.as_str() != Some("null")) {
+      Some(branch) => avro_type_into_serde_schema(branch),
+      None => Ok(empty_serde_schema(Some("null")))
+    },
+    _ => Err(Error::validation(
+      "Invalid Avro type.",
+      "Use a type name, a complex type object, or a union array."
+    ))
+  }
+}
+
+/// Transforms an Avro primitive type name into the equivalent `SerdeSchema`.
+/// `int`/`long` become a number constrained to whole values via
+/// `multipleOf = 1.0`; `float`/`double` become an unconstrained number;
+/// `bytes` is treated like `string` since Ardite has no dedicated binary
+/// type.
+fn avro_primitive_into_serde_schema(name: &str) -> Result<SerdeSchema, Error> {
+  match name {
+    "null" => Ok(empty_serde_schema(Some("null"))),
+    "boolean" => Ok(empty_serde_schema(Some("boolean"))),
+    "int" | "long" => Ok(SerdeSchema { multiple_of: Some(1.0), ..empty_serde_schema(Some("number")) }),
+    "float" | "double" => Ok(empty_serde_schema(Some("number"))),
+    "string" | "bytes" => Ok(empty_serde_schema(Some("string"))),
+    _ => Err(Error::validation(
+      format!("Unrecognized Avro primitive type '{}'.", name),
+      "Use a permitted Avro primitive like 'int', 'string', or 'boolean', or define a complex type inline."
+    ))
+  }
+}
+
+/// Transforms an Avro complex type object (`record`, `array`, or `enum`)
+/// into the equivalent `SerdeSchema`.
+fn avro_complex_into_serde_schema(avro: &serde_json::Value) -> Result<SerdeSchema, Error> {
+  let type_ = try!(avro.get("type").and_then(|type_| type_.as_str()).ok_or_else(|| Error::validation(
+    "Avro complex type is missing a 'type' string.",
+    "Set a 'type' property like 'record', 'array', or 'enum'."
+  )));
+
+  match type_ {
+    "record" => {
+      let empty = Vec::new();
+      let fields = avro.get("fields").and_then(|fields| fields.as_array()).unwrap_or(&empty);
+      let mut properties = BTreeMap::new();
+      let mut required = Vec::new();
+      for field in fields {
+        let (name, field_schema, is_required) = try!(avro_field_into_property(field));
+        if is_required { required.push(name.clone()); }
+        properties.insert(name, field_schema);
+      }
+      Ok(SerdeSchema { properties: Some(properties), required: Some(required), ..empty_serde_schema(Some("object")) })
+    },
+    "array" => {
+      let items = try!(avro.get("items").ok_or_else(|| Error::validation(
+        "Avro array type is missing an 'items' type.",
+        "Set an 'items' property describing the array's elements."
+      )));
+      Ok(SerdeSchema { items: Some(Box::new(try!(avro_type_into_serde_schema(items)))), ..empty_serde_schema(Some("array")) })
+    },
+    "enum" => {
+      let symbols = try!(avro.get("symbols").and_then(|symbols| symbols.as_array()).ok_or_else(|| Error::validation(
+        "Avro enum type is missing a 'symbols' array.",
+        "Set a 'symbols' property listing the enum's allowed values."
+      )));
+      // No `type` is set here: an "enum" schema is recognized by `to_schema`
+      // via its `enum_` field alone (see the `None => ...` arm), the same way
+      // a hand-written definition file would declare one.
+      Ok(SerdeSchema {
+        enum_: Some(symbols.iter().filter_map(|symbol| symbol.as_str().map(|s| Value::String(s.to_owned()))).collect()),
+        ..empty_serde_schema(None)
+      })
+    },
+    _ => Err(Error::validation(
+      format!("Unsupported Avro complex type '{}'.", type_),
+      "Use a supported Avro type like 'record', 'array', or 'enum'."
+    ))
+  }
+}
+
+/// Transforms a single Avro record field into an object property, returning
+/// its name, its schema, and whether it is required. A field typed as a
+/// two-branch `["null", T]` union is treated as an optional (non-required)
+/// property of `T`, per Avro's convention for optional fields.
+fn avro_field_into_property(field: &serde_json::Value) -> Result<(String, SerdeSchema, bool), Error> {
+  let name = try!(field.get("name").and_then(|name| name.as_str()).ok_or_else(|| Error::validation(
+    "Avro field is missing a 'name'.",
+    "Give every field in the 'fields' array a 'name'."
+  ))).to_owned();
+  let field_type = try!(field.get("type").ok_or_else(|| Error::validation(
+    format!("Avro field '{}' is missing a 'type'.", name),
+    "Give every field in the 'fields' array a 'type'."
+  )));
+
+  if let serde_json::Value::Array(ref union) = *field_type {
+    if union.len() == 2 && union.iter().any(|branch| branch.as_str() == Some("null")) {
+      let branch = union.iter().find(|branch| branch.as_str() != Some("null")).unwrap();
+      return Ok((name, try!(avro_type_into_serde_schema(branch)), false));
+    }
+  }
+
+  Ok((name, try!(avro_type_into_serde_schema(field_type)), true))
+}
+
 #[cfg(test)]
 mod tests {
   use std::path::PathBuf;
   use definition::Definition;
   use definition::schema::Schema;
   use definition::serde::from_file;
-  
+  use regex::Regex;
+
   lazy_static! {
     static ref BASIC_DEFINITION: Definition = Definition {
       data: Schema::Object {
@@ -144,7 +484,7 @@ mod tests {
                 String::from("email") => Schema::String {
                   min_length: Some(4),
                   max_length: Some(256),
-                  pattern: Some(String::from(r".+@.+\..+"))
+                  pattern: Some(Regex::new(r".+@.+\..+").unwrap())
                 },
                 String::from("name") => Schema::String {
                   min_length: Some(2),
@@ -177,15 +517,52 @@ mod tests {
       }
     };
   }
-  
+
   #[test]
   fn test_basic_json() {
     assert_eq!(from_file(PathBuf::from("tests/fixtures/definitions/basic.json")).unwrap(), *BASIC_DEFINITION);
   }
-  
+
   #[test]
-  #[ignore]
   fn test_basic_yaml() {
     assert_eq!(from_file(PathBuf::from("tests/fixtures/definitions/basic.yml")).unwrap(), *BASIC_DEFINITION);
   }
+
+  #[test]
+  fn test_basic_json5() {
+    assert_eq!(from_file(PathBuf::from("tests/fixtures/definitions/basic.json5")).unwrap(), *BASIC_DEFINITION);
+  }
+
+  #[test]
+  fn test_composition_and_ref_and_typed_enum() {
+    let definition = from_file(PathBuf::from("tests/fixtures/definitions/composition.json")).unwrap();
+    assert_eq!(definition, Definition {
+      data: Schema::AllOf(vec![
+        Schema::Object {
+          required: vec![],
+          additional_properties: false,
+          properties: linear_map! {
+            String::from("status") => Schema::Enum(vec![vstring!("ok"), vi64!(404), vbool!(true)]),
+            String::from("count") => Schema::Number {
+              multiple_of: None,
+              minimum: Some(0.0),
+              exclusive_minimum: false,
+              maximum: None,
+              exclusive_maximum: false
+            }
+          }
+        },
+        Schema::Object {
+          required: vec![],
+          additional_properties: false,
+          properties: linear_map! {}
+        }
+      ])
+    });
+  }
+
+  #[test]
+  fn test_cyclic_ref() {
+    from_file(PathBuf::from("tests/fixtures/definitions/cyclic.json")).unwrap_err().assert_message("Cyclic");
+  }
 }
@@ -0,0 +1,133 @@
+//! A driver adapter which memoizes reads in memory so that read-heavy
+//! workloads avoid repeated round-trips to a slower underlying driver.
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use url::Url;
+
+use driver::{ChangeIter, Driver, Iter};
+use error::Error;
+use patch::Patch;
+use query::{Condition, Range, Sort};
+use transaction::{Transaction, TransactionOp, TransactionResult};
+use value::{Object, Value};
+
+/// A structural key identifying a single `read`/`read_one` call. `Condition`,
+/// `Sort`, and `Range` don’t derive `Hash` (they ultimately contain `Value`,
+/// which holds an `f64` and so can’t implement `Eq`/`Hash` itself), so the
+/// key is built from their canonical `Debug` representation instead. This is
+/// cheap, structural (two equal queries always format identically), and
+/// avoids having to hand-write a parallel hashing scheme for every type in
+/// the query DSL.
+#[derive(PartialEq, Eq, Hash)]
+struct CacheKey(String);
+
+impl CacheKey {
+  fn new(name: &str, condition: &Condition, sorts: &[Sort], range: &Range) -> Self {
+    CacheKey(format!("{}|{:?}|{:?}|{:?}", name, condition, sorts, range))
+  }
+}
+
+/// Wraps any `Driver` and memoizes `read`/`read_one` results keyed by the
+/// collection name, condition, sort, and range of the call. Because `Iter` is
+/// lazy, a cache hit must still hand out a fresh iterator, so the first read
+/// for a key fully materializes the wrapped driver’s iterator into a `Vec`
+/// and every subsequent identical read re-iterates that `Vec` instead of
+/// touching the wrapped driver again.
+///
+/// A mutation through `create`, `patch`, or `delete` invalidates every cached
+/// read for the affected collection name, since any of those entries could
+/// now be stale.
+pub struct CachingDriver<D: Driver> {
+  /// The wrapped driver which is actually queried on a cache miss.
+  driver: D,
+  /// Cached `read` results, keyed by the call which produced them.
+  cache: Mutex<HashMap<CacheKey, Vec<Object>>>
+}
+
+impl<D: Driver> CachingDriver<D> {
+  /// Wraps `driver` in a new, empty cache.
+  pub fn new(driver: D) -> Self {
+    CachingDriver {
+      driver: driver,
+      cache: Mutex::new(HashMap::new())
+    }
+  }
+
+  /// Drops every cached read for `name`, forcing the next read of that
+  /// collection to go back to the wrapped driver.
+  fn invalidate(&self, name: &str) {
+    let prefix = format!("{}|", name);
+    let mut cache = self.cache.lock().unwrap();
+    let stale: Vec<CacheKey> = cache.keys().filter(|key| key.0.starts_with(&prefix)).map(|key| CacheKey(key.0.clone())).collect();
+
+    for key in stale {
+      cache.remove(&key);
+    }
+  }
+}
+
+impl<D: Driver> Driver for CachingDriver<D> {
+  fn connect(url: &Url) -> Result<Self, Error> {
+    D::connect(url).map(CachingDriver::new)
+  }
+
+  fn read(
+    &self,
+    name: &str,
+    condition: Condition,
+    sorts: Vec<Sort>,
+    range: Range
+  ) -> Result<Iter, Error> {
+    let key = CacheKey::new(name, &condition, &sorts, &range);
+
+    if let Some(objects) = self.cache.lock().unwrap().get(&key) {
+      return Ok(Iter::new(objects.clone().into_iter()));
+    }
+
+    let objects: Vec<Object> = try!(self.driver.read(name, condition, sorts, range)).collect();
+    self.cache.lock().unwrap().insert(key, objects.clone());
+    Ok(Iter::new(objects.into_iter()))
+  }
+
+  fn create(&self, name: &str, value: Value) -> Result<Value, Error> {
+    let value = try!(self.driver.create(name, value));
+    self.invalidate(name);
+    Ok(value)
+  }
+
+  fn patch(&self, name: &str, condition: Condition, patches: Vec<Patch>) -> Result<Iter, Error> {
+    let iter = try!(self.driver.patch(name, condition, patches));
+    self.invalidate(name);
+    Ok(iter)
+  }
+
+  fn delete(&self, name: &str, condition: Condition) -> Result<u64, Error> {
+    let count = try!(self.driver.delete(name, condition));
+    self.invalidate(name);
+    Ok(count)
+  }
+
+  // Change-stream subscriptions aren’t cacheable; pass straight through to
+  // the wrapped driver.
+  fn watch(&self, name: &str, condition: Condition) -> Result<ChangeIter, Error> {
+    self.driver.watch(name, condition)
+  }
+
+  fn apply_transaction(&self, tx: Transaction) -> Result<TransactionResult, Error> {
+    let names: Vec<&str> = tx.ops().iter().filter_map(|op| match *op {
+      TransactionOp::Set(ref pointer, _) => pointer.get(0).map(String::as_ref),
+      TransactionOp::Insert(ref collection, _) => Some(collection.as_ref()),
+      TransactionOp::Delete(ref collection, _) => Some(collection.as_ref())
+    }).collect();
+
+    let result = try!(self.driver.apply_transaction(tx));
+
+    for name in names {
+      self.invalidate(name);
+    }
+
+    Ok(result)
+  }
+}
@@ -1,24 +1,71 @@
+use std::collections::HashMap;
+
 use driver::Driver;
 use error::Error;
 use schema;
 
-/// Takes a driver config value and finds and connects the associated driver.
-/// Errors if not driver could be found for the given config.
-// TODO: @svmnotn this is your jam!
-pub fn discover_driver(config: &schema::Driver) -> Result<Box<Driver>, Error> {
-  match config.url().scheme.as_str() {
-    "mongodb" => connect_mongodb_driver(config),
-    _ => Err(Error::not_found(format!("Driver for URL '{}' not found.", config.url())))
+/// Maps a URL scheme (e.g. `"mongodb"`) to the connector used to connect a
+/// `Driver` for it, so adding a driver is a `register` call instead of an
+/// edit to this module. `DriverRegistry::new` comes pre-populated with every
+/// driver compiled into this crate (just `mongodb`, behind `driver_mongodb`,
+/// for now)—library users register their own connectors for custom schemes
+/// on top of that before calling `connect`.
+pub struct DriverRegistry {
+  connectors: HashMap<String, Box<Fn(&schema::Driver) -> Result<Box<Driver>, Error>>>
+}
+
+impl DriverRegistry {
+  /// Creates a registry pre-populated with the built-in drivers.
+  pub fn new() -> Self {
+    let mut registry = DriverRegistry {
+      connectors: HashMap::new()
+    };
+    registry.register_builtins();
+    registry
+  }
+
+  #[cfg(feature = "driver_mongodb")]
+  fn register_builtins(&mut self) {
+    self.register("mongodb", connect_mongodb_driver);
+  }
+
+  #[cfg(not(feature = "driver_mongodb"))]
+  fn register_builtins(&mut self) {}
+
+  /// Registers the connector to use for `scheme`, overwriting any existing
+  /// registration (including a built-in one) for it.
+  pub fn register<F>(&mut self, scheme: &str, connector: F) where F: Fn(&schema::Driver) -> Result<Box<Driver>, Error> + 'static {
+    self.connectors.insert(scheme.to_owned(), Box::new(connector));
+  }
+
+  /// Dispatches on `config`'s URL scheme to find and connect the associated
+  /// driver. Errors if no connector is registered for the scheme.
+  pub fn connect(&self, config: &schema::Driver) -> Result<Box<Driver>, Error> {
+    match self.connectors.get(config.url().scheme.as_str()) {
+      Some(connector) => connector(config),
+      None => Err(Error::not_found(format!("Driver for URL '{}' not found.", config.url())))
+    }
   }
 }
 
-#[cfg(feature = "driver_mongodb")]
-fn connect_mongodb_driver(_: &schema::Driver) -> Result<Box<Driver>, Error> {
-  use driver::mongodb::MongoDB;
-  MongoDB::connect(config.url()).map(Box::new)
+impl Default for DriverRegistry {
+  fn default() -> Self {
+    DriverRegistry::new()
+  }
 }
 
-#[cfg(not(feature = "driver_mongodb"))]
-fn connect_mongodb_driver(_: &schema::Driver) -> Result<Box<Driver>, Error> {
-  Err(Error::invalid("Can not use MongoDB driver.", "Try compiling Ardite with the `driver_mongodb` feature enabled."))
+/// Takes a driver config value and finds and connects the associated driver
+/// using a registry of just the built-in drivers. Errors if no driver could
+/// be found for the given config.
+///
+/// Build a `DriverRegistry` directly (and use `DriverRegistry::connect`
+/// instead) if custom, out-of-tree drivers need to participate in discovery.
+pub fn discover_driver(config: &schema::Driver) -> Result<Box<Driver>, Error> {
+  DriverRegistry::new().connect(config)
+}
+
+#[cfg(feature = "driver_mongodb")]
+fn connect_mongodb_driver(config: &schema::Driver) -> Result<Box<Driver>, Error> {
+  use driver::mongodb::MongoDB;
+  MongoDB::connect(config.url()).map(|driver| Box::new(driver) as Box<Driver>)
 }
@@ -3,15 +3,41 @@
 
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::sync::mpsc::{self, Sender};
 use std::sync::Mutex;
 
 use itertools::Itertools;
 use url::Url;
 
-use driver::{Driver, Iter};
+use driver::{ChangeEvent, ChangeIter, ChangeKind, Driver, Iter};
 use error::Error;
+use patch::Patch;
 use query::{Range, Sort, Condition};
-use value::Object;
+use transaction::{Transaction, TransactionOp, TransactionOpResult, TransactionResult};
+use value::{Object, Value};
+
+/// A single named collection: its rows, plus anyone currently `watch`ing it.
+#[derive(Clone)]
+struct Collection {
+  objects: Vec<Object>,
+  watchers: Vec<Sender<ChangeEvent>>
+}
+
+impl Collection {
+  fn new() -> Self {
+    Collection {
+      objects: Vec::new(),
+      watchers: Vec::new()
+    }
+  }
+
+  /// Broadcasts `kind`/`object` to every live watcher, dropping any whose
+  /// receiving end has gone away.
+  fn notify(&mut self, kind: ChangeKind, object: &Object) {
+    let event = ChangeEvent { kind: kind, object: object.clone() };
+    self.watchers.retain(|watcher| watcher.send(event.clone()).is_ok());
+  }
+}
 
 /// The default driver to be used by a service when no other driver is
 /// specified. This driver, unlike the others, stores all of its data in
@@ -24,7 +50,7 @@ use value::Object;
 pub struct Memory {
   /// The actual internal `HashMap` store. Wrapped in a `Mutex` so that we can
   /// mutate the value *without* requiring a mutable reference to `Memory`.
-  store: Mutex<HashMap<String, Vec<Object>>>
+  store: Mutex<HashMap<String, Collection>>
 }
 
 impl Memory {
@@ -42,14 +68,13 @@ impl Memory {
   /// shared across multiple different threads.
   pub fn append_to_collection(&self, name: &str, objects: &mut Vec<Object>) {
     let mut store = self.store.lock().unwrap();
+    let collection = store.entry(name.to_owned()).or_insert_with(Collection::new);
 
-    if !store.contains_key(name) {
-      store.insert(name.to_owned(), Vec::new());
+    for object in objects.iter() {
+      collection.notify(ChangeKind::Insert, object);
     }
 
-    // We can safely unwrap here because we guarantee the collection exists in
-    // the if statement above.
-    store.get_mut(name).unwrap().append(objects);
+    collection.objects.append(objects);
   }
 }
 
@@ -74,10 +99,10 @@ impl Driver for Memory {
     sorts: Vec<Sort>,
     range: Range
   ) -> Result<Iter, Error> {
-    if let Some(objects) = self.store.lock().unwrap().get(name) {
+    if let Some(collection) = self.store.lock().unwrap().get(name) {
       Ok(Iter::new(
-        objects
-        .into_iter()
+        collection.objects
+        .iter()
         .filter(|object| cond.is_object_true(object))
         .slice(range)
         .cloned()
@@ -93,4 +118,156 @@ impl Driver for Memory {
       Ok(Iter::none())
     }
   }
+
+  fn create(&self, name: &str, value: Value) -> Result<Value, Error> {
+    let object = match value {
+      Value::Object(ref object) => object.clone(),
+      _ => return Err(Error::invalid(
+        "Can’t create a non-object value.",
+        "Try creating an object value instead."
+      ))
+    };
+    self.append_to_collection(name, &mut vec![object]);
+    Ok(value)
+  }
+
+  // TODO: The memory driver has no schema of its own to resolve a `Reset`
+  // patch’s default value against, so it falls back to `Value::Null`.
+  fn patch(&self, name: &str, cond: Condition, patches: Vec<Patch>) -> Result<Iter, Error> {
+    let mut store = self.store.lock().unwrap();
+
+    if let Some(collection) = store.get_mut(name) {
+      let mut updated = Vec::new();
+
+      for object in collection.objects.iter_mut() {
+        if cond.is_object_true(object) {
+          let mut value = Value::Object(object.clone());
+
+          for patch in &patches {
+            value = try!(value.apply_patch(patch, &Value::Null(())));
+          }
+
+          if let Value::Object(new_object) = value {
+            *object = new_object.clone();
+            updated.push(new_object);
+          }
+        }
+      }
+
+      for object in &updated {
+        collection.notify(ChangeKind::Update, object);
+      }
+
+      Ok(Iter::new(updated.into_iter()))
+    } else {
+      Ok(Iter::none())
+    }
+  }
+
+  fn delete(&self, name: &str, cond: Condition) -> Result<u64, Error> {
+    let mut store = self.store.lock().unwrap();
+
+    if let Some(collection) = store.get_mut(name) {
+      let original_len = collection.objects.len();
+      let (removed, kept): (Vec<Object>, Vec<Object>) = collection.objects.drain(..).partition(|object| cond.is_object_true(object));
+      collection.objects = kept;
+
+      for object in &removed {
+        collection.notify(ChangeKind::Delete, object);
+      }
+
+      Ok((original_len - collection.objects.len()) as u64)
+    } else {
+      Ok(0)
+    }
+  }
+
+  /// Subscribes to live mutations of `name` by pushing a broadcast `Sender`
+  /// into the collection's watcher list, guarded by the same `Mutex` as the
+  /// data itself so a subscription can never race a concurrent write.
+  fn watch(&self, name: &str, cond: Condition) -> Result<ChangeIter, Error> {
+    let mut store = self.store.lock().unwrap();
+    let collection = store.entry(name.to_owned()).or_insert_with(Collection::new);
+    let (sender, receiver) = mpsc::channel();
+    collection.watchers.push(sender);
+
+    Ok(ChangeIter::new(
+      receiver.into_iter().filter(move |event| cond.is_object_true(&event.object))
+    ))
+  }
+
+  // We hold the store `Mutex` for the whole batch and stage every op into a
+  // clone, only swapping it into `self.store` once every op has succeeded—so
+  // a concurrent reader (or `watch`er) never observes a partial transaction.
+  fn apply_transaction(&self, tx: Transaction) -> Result<TransactionResult, Error> {
+    let mut store = self.store.lock().unwrap();
+    let mut staged = store.clone();
+    let mut results = Vec::new();
+    let mut events: Vec<(String, ChangeKind, Object)> = Vec::new();
+
+    for op in tx.into_ops() {
+      match op {
+        TransactionOp::Insert(collection, object) => {
+          staged.entry(collection.clone()).or_insert_with(Collection::new).objects.push(object.clone());
+          events.push((collection, ChangeKind::Insert, object.clone()));
+          results.push(TransactionOpResult::Insert(Value::Object(object)));
+        },
+        TransactionOp::Delete(collection, condition) => {
+          if let Some(target) = staged.get_mut(&collection) {
+            let original_len = target.objects.len();
+            let (removed, kept): (Vec<Object>, Vec<Object>) = target.objects.drain(..).partition(|object| condition.is_object_true(object));
+            target.objects = kept;
+
+            for object in removed {
+              events.push((collection.clone(), ChangeKind::Delete, object));
+            }
+
+            results.push(TransactionOpResult::Delete((original_len - target.objects.len()) as u64));
+          } else {
+            results.push(TransactionOpResult::Delete(0));
+          }
+        },
+        TransactionOp::Set(pointer, value) => {
+          if pointer.len() < 2 {
+            return Err(Error::invalid(
+              "A transaction `Set` pointer must start with a collection name and a document id.",
+              "Use a pointer like [\"collection\", \"id\", ...path-within-document]."
+            ));
+          }
+
+          let collection = pointer[0].clone();
+          let id = pointer[1].clone();
+          let path = pointer[2..].to_vec();
+          let target = staged.entry(collection.clone()).or_insert_with(Collection::new);
+          let index = target.objects.iter().position(|object| object.get("id") == Some(&Value::String(id.clone())));
+
+          match index {
+            Some(index) => {
+              let patch = Patch::Set(path, value);
+              let patched = try!(Value::Object(target.objects[index].clone()).apply_patch(&patch, &Value::Null(())));
+
+              if let Value::Object(new_object) = patched {
+                target.objects[index] = new_object.clone();
+                events.push((collection, ChangeKind::Update, new_object.clone()));
+                results.push(TransactionOpResult::Set(Value::Object(new_object)));
+              } else {
+                return Err(Error::internal("Patching a transaction `Set` target produced a non-object value."));
+              }
+            },
+            None => return Err(Error::not_found(format!("No document with id '{}' was found in '{}'.", id, collection)))
+          }
+        }
+      }
+    }
+
+    *store = staged;
+
+    for (collection, kind, object) in events {
+      if let Some(target) = store.get_mut(&collection) {
+        target.notify(kind, &object);
+      }
+    }
+
+    Ok(TransactionResult::new(results))
+  }
 }
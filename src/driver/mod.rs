@@ -1,12 +1,14 @@
 //! This module contains the common driver code. Specific implementations for
 //! different drivers exist elsewhere.
 
+mod caching;
 mod discover;
 mod memory;
 #[cfg(feature = "driver_mongodb")]
 pub mod mongodb;
 
-pub use self::discover::discover_driver;
+pub use self::caching::CachingDriver;
+pub use self::discover::{discover_driver, DriverRegistry};
 pub use self::memory::Memory;
 
 use std::iter;
@@ -14,8 +16,10 @@ use std::iter;
 use url::Url;
 
 use error::Error;
+use patch::Patch;
 use query::{Condition, Sort, Range};
-use value::Object;
+use transaction::{Transaction, TransactionResult};
+use value::{Object, Value};
 
 /// The driver trait which all drivers will implement. Designed to be
 /// interoperable with any data source, however the driver also assumes a
@@ -75,6 +79,60 @@ pub trait Driver: Send + Sync {
       Err(Error::not_found("No value was found for the condition."))
     }
   }
+
+  /// Creates a new value in the driver, mirroring a SQL `INSERT` or a MongoDB
+  /// `insert` command. Returns the created value, which may differ from the
+  /// value that was passed in if the driver assigns generated fields (for
+  /// example an auto-incrementing id).
+  fn create(&self, name: &str, value: Value) -> Result<Value, Error>;
+
+  /// Applies a set of `Patch`es to every value matched by `condition`,
+  /// mirroring a SQL `UPDATE` or a MongoDB `update` command.
+  ///
+  /// Each patch is applied against the pointer-addressed sub-value of a
+  /// matched document: `Patch::Set` replaces the value at the path,
+  /// `Patch::Reset` restores it to the schema default, and `Patch::Remove`
+  /// deletes the key. The updated documents are returned so callers can
+  /// observe the server-side changes.
+  fn patch(&self, name: &str, condition: Condition, patches: Vec<Patch>) -> Result<Iter, Error>;
+
+  /// Deletes every value matched by `condition`, mirroring a SQL `DELETE` or
+  /// a MongoDB `delete` command. Returns the number of values deleted.
+  fn delete(&self, name: &str, condition: Condition) -> Result<u64, Error>;
+
+  /// Subscribes to an ongoing stream of changes made to `name` matching
+  /// `condition`, mirroring a MongoDB [change stream][1]. Lets a caller drive
+  /// a reactive layer, or select on a raw pollable handle from an external
+  /// event loop, rather than polling `read` repeatedly.
+  ///
+  /// [1]: https://docs.mongodb.com/manual/changeStreams/
+  fn watch(&self, name: &str, condition: Condition) -> Result<ChangeIter, Error>;
+
+  /// Applies every operation staged on `tx` atomically: either they all
+  /// commit, or none of them do. Returns the per-operation results in the
+  /// same order the operations were staged.
+  fn apply_transaction(&self, tx: Transaction) -> Result<TransactionResult, Error>;
+}
+
+/// The kind of mutation that produced a `ChangeEvent`.
+#[derive(PartialEq, Clone, Debug)]
+pub enum ChangeKind {
+  /// A new value was created.
+  Insert,
+  /// An existing value was patched.
+  Update,
+  /// A value was deleted.
+  Delete
+}
+
+/// A single change observed by `Driver::watch`.
+#[derive(Clone, Debug)]
+pub struct ChangeEvent {
+  /// The kind of mutation that occurred.
+  pub kind: ChangeKind,
+  /// The affected object, as it looks after the change (for `Insert` and
+  /// `Update`) or as it looked before being removed (for `Delete`).
+  pub object: Object
 }
 
 /// An iterator of values. Used by drivers to convert their own iterator
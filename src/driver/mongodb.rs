@@ -7,9 +7,10 @@ use mongodb::connstring;
 use mongodb::db::{Database, ThreadedDatabase};
 use url::Url;
 
-use driver::Driver;
+use driver::{ChangeEvent, ChangeIter, ChangeKind, Driver};
 use error::Error;
 use query::{Range, Sort, Condition};
+use transaction::{Transaction, TransactionOp, TransactionOpResult, TransactionResult};
 use value::{Value, Iter};
 
 pub struct MongoDB {
@@ -70,11 +71,166 @@ impl Driver for MongoDB {
 
     Ok(Iter::new(cursor.filter_map(Result::ok).map(Value::from)))
   }
+
+  /// Watches `name` with a `$changeStream` aggregation pipeline cursor,
+  /// filtering down to matching documents with a `$match` stage built from
+  /// the existing `condition_to_filter`, with every field path prefixed by
+  /// `fullDocument.` via `prefix_full_document_fields` (a change event's
+  /// matched document lives under that key, unlike a plain `find` filter).
+  fn watch(&self, name: &str, condition: Condition) -> Result<ChangeIter, Error> {
+    let match_stage = prefix_full_document_fields(condition_to_filter(condition));
+
+    let spec = doc! {
+      "aggregate" => name,
+      "pipeline" => [
+        { "$changeStream" => { "fullDocument" => "updateLookup" } },
+        { "$match" => match_stage }
+      ],
+      "cursor" => {}
+    };
+
+    let cursor = try!(self.database.command_cursor(spec, CommandType::Aggregate, ReadPreference {
+      // Change streams are tailed against the primary so we observe writes
+      // as they happen, rather than risking staleness on a secondary.
+      mode: ReadMode::Primary,
+      tag_sets: vec![]
+    }));
+
+    Ok(ChangeIter::new(cursor.filter_map(Result::ok).filter_map(document_to_change_event)))
+  }
+
+  /// Applies each staged operation in order with a plain `insert`/`update`/
+  /// `delete` command.
+  ///
+  /// This driver predates MongoDB client sessions and the
+  /// `startTransaction`/`commitTransaction`/`abortTransaction` commands that
+  /// would let several operations commit or roll back together, so it
+  /// cannot honor atomicity for more than one staged operation: a failure
+  /// partway through a multi-operation transaction would leave the earlier
+  /// operations applied with no way to undo them. Rather than silently
+  /// applying those best-effort, a transaction with more than one operation
+  /// is rejected outright; a single operation is trivially atomic and is
+  /// applied as normal.
+  ///
+  /// A `Set` is applied as a `$set` on the addressed field, and its result
+  /// holds the value that was set, not the updated document.
+  fn apply_transaction(&self, tx: Transaction) -> Result<TransactionResult, Error> {
+    if tx.ops().len() > 1 {
+      return Err(Error::unimplemented(
+        "This driver cannot yet apply a multi-operation transaction atomically, so it refuses to apply one rather than risk a partial write."
+      ));
+    }
+
+    let mut results = Vec::new();
+
+    for op in tx.into_ops() {
+      let result = match op {
+        TransactionOp::Insert(collection, object) => {
+          let document: Document = Value::Object(object.clone()).into();
+          let spec = doc! {
+            "insert" => collection,
+            "documents" => [document]
+          };
+          try!(self.database.command(spec, CommandType::Suppressed, None));
+          TransactionOpResult::Insert(Value::Object(object))
+        },
+        TransactionOp::Delete(collection, condition) => {
+          let spec = doc! {
+            "delete" => collection,
+            "deletes" => [{ "q" => (condition_to_filter(condition)), "limit" => 0 }]
+          };
+          let reply = try!(self.database.command(spec, CommandType::Suppressed, None));
+          let deleted = match reply.get("n") {
+            Some(&Bson::I32(n)) => n as u64,
+            Some(&Bson::I64(n)) => n as u64,
+            _ => 0
+          };
+          TransactionOpResult::Delete(deleted)
+        },
+        TransactionOp::Set(pointer, value) => {
+          if pointer.len() < 2 {
+            return Err(Error::invalid(
+              "A transaction `Set` pointer must start with a collection name and a document id.",
+              "Use a pointer like [\"collection\", \"id\", ...path-within-document]."
+            ));
+          }
+
+          let field = pointer[2..].join(".");
+          let bson_value: Bson = value.clone().into();
+          let mut set_document = Document::new();
+          set_document.insert(if field.is_empty() { String::from("value") } else { field }, bson_value);
+
+          let spec = doc! {
+            "update" => (pointer[0].clone()),
+            "updates" => [{
+              "q" => { "id" => (pointer[1].clone()) },
+              "u" => { "$set" => set_document }
+            }]
+          };
+          try!(self.database.command(spec, CommandType::Suppressed, None));
+          TransactionOpResult::Set(value)
+        }
+      };
+
+      results.push(result);
+    }
+
+    Ok(TransactionResult::new(results))
+  }
 }
 
+/// Converts a MongoDB change-stream event document into a `ChangeEvent`,
+/// discarding anything we can't classify: events without an `operationType`
+/// we recognize, or without the `fullDocument` our `updateLookup` asked for
+/// (notably a `delete`, which MongoDB never attaches a `fullDocument` to).
+fn document_to_change_event(document: Document) -> Option<ChangeEvent> {
+  let kind = match document.get("operationType") {
+    Some(&Bson::String(ref operation_type)) => match operation_type.as_ref() {
+      "insert" => ChangeKind::Insert,
+      "update" | "replace" => ChangeKind::Update,
+      "delete" => ChangeKind::Delete,
+      _ => return None
+    },
+    _ => return None
+  };
+
+  match document.get("fullDocument") {
+    Some(&Bson::Document(ref full_document)) => match Value::from(full_document.clone()) {
+      Value::Object(object) => Some(ChangeEvent { kind: kind, object: object }),
+      _ => None
+    },
+    _ => None
+  }
+}
+
+/// Server error codes the MongoDB documentation calls out as transient:
+/// stepdowns and "not (yet) writable primary" errors (10107, 13435, 13436,
+/// 189, 91), interrupted/network-timeout style errors (11600, 11602, 7, 6,
+/// 89), and the `ExceededTimeLimit` a retryable write can surface (9001).
+///
+/// This driver integration only ever sees a `mongodb::Error` through its
+/// `Display` impl (the crate doesn't expose the server error code on the
+/// error type itself), so that's what we match the codes against here.
+static RETRYABLE_SERVER_CODES: [u32; 11] = [11600, 11602, 10107, 13435, 13436, 189, 91, 7, 6, 89, 9001];
+
 impl From<mongodb::Error> for Error {
   fn from(error: mongodb::Error) -> Self {
-    Error::internal(format!("{}", error))
+    let message = format!("{}", error);
+
+    // Pull out whole runs of digits rather than testing substring
+    // containment: a short code like `6`, `7`, or `91` would otherwise
+    // false-positive against unrelated numbers (ids, byte offsets, other
+    // codes) that merely contain those digits.
+    let retryable = message
+      .split(|c: char| !c.is_ascii_digit())
+      .filter_map(|run| run.parse::<u32>().ok())
+      .any(|code| RETRYABLE_SERVER_CODES.contains(&code));
+
+    if retryable {
+      Error::retryable_internal(message)
+    } else {
+      Error::internal(message)
+    }
   }
 }
 
@@ -110,6 +266,9 @@ impl Into<Bson> for Value {
       Value::Null(_) => Bson::Null,
       Value::Boolean(value) => Bson::Boolean(value),
       Value::I64(value) => Bson::I64(value),
+      // BSON has no unsigned 64-bit integer type, so the closest lossless
+      // representation is a double; values beyond 2^53 will lose precision.
+      Value::U64(value) => Bson::FloatingPoint(value as f64),
       Value::F64(value) => Bson::FloatingPoint(value),
       Value::String(value) => Bson::String(value),
       value @ Value::Object(_) => Bson::Document(value.into()),
@@ -143,6 +302,36 @@ impl Into<Document> for Value {
   }
 }
 
+/// Rewrites a `condition_to_filter` document so every field path is prefixed
+/// with `fullDocument.`, as required to match against a change-stream
+/// event's matched document rather than the top-level document a plain
+/// `find` filter addresses. `$and`/`$or`/`$not` are logical combinators, not
+/// field paths, so their nested condition documents are recursed into
+/// instead of being prefixed themselves; `$where` holds a raw JS expression
+/// and is left untouched.
+fn prefix_full_document_fields(filter: Bson) -> Bson {
+  let document = match filter {
+    Bson::Document(document) => document,
+    other => return other
+  };
+
+  let mut prefixed = Document::new();
+  for (key, value) in document.into_iter() {
+    match key.as_ref() {
+      "$and" | "$or" => match value {
+        Bson::Array(conditions) => {
+          prefixed.insert(key, Bson::Array(conditions.into_iter().map(prefix_full_document_fields).collect()));
+        },
+        other => { prefixed.insert(key, other); }
+      },
+      "$not" => { prefixed.insert(key, prefix_full_document_fields(value)); },
+      "$where" => { prefixed.insert(key, value); },
+      _ => { prefixed.insert(format!("fullDocument.{}", key), value); }
+    }
+  }
+  Bson::Document(prefixed)
+}
+
 /// Transforms an Ardite condition to a MongoDB filter as specified by the
 /// MongoDB spec.
 pub fn condition_to_filter(condition: Condition) -> Bson {
@@ -181,7 +370,27 @@ pub fn condition_to_filter(condition: Condition) -> Bson {
     Condition::Equal(value) => {
       let bson_value: Bson = value.into();
       bson!({ "$eq" => bson_value })
-    }
+    },
+    Condition::GreaterThan(value) => {
+      let bson_value: Bson = value.into();
+      bson!({ "$gt" => bson_value })
+    },
+    Condition::GreaterThanOrEqual(value) => {
+      let bson_value: Bson = value.into();
+      bson!({ "$gte" => bson_value })
+    },
+    Condition::LessThan(value) => {
+      let bson_value: Bson = value.into();
+      bson!({ "$lt" => bson_value })
+    },
+    Condition::LessThanOrEqual(value) => {
+      let bson_value: Bson = value.into();
+      bson!({ "$lte" => bson_value })
+    },
+    Condition::In(values) => bson!({
+      "$in" => (Bson::Array(values.into_iter().map(Value::into).collect()))
+    }),
+    Condition::Matches(regex) => bson!({ "$regex" => (regex.as_str()) })
   }
 }
 
@@ -246,6 +455,78 @@ mod tests {
     assert_eq!(condition_to_filter(condition), filter);
   }
 
+  #[test]
+  fn test_condition_to_filter_comparisons() {
+    use query::Condition::*;
+    let condition = Key(str!("a"), Box::new(And(vec![
+      GreaterThan(value!(5)),
+      LessThanOrEqual(value!(10))
+    ])));
+    let filter = bson!({
+      "a" => {
+        "$and" => [
+          { "$gt" => 5i64 },
+          { "$lte" => 10i64 }
+        ]
+      }
+    });
+    assert_eq!(condition_to_filter(condition), filter);
+  }
+
+  #[test]
+  fn test_condition_to_filter_in_and_matches() {
+    use query::Condition::*;
+    let condition = Key(str!("a"), Box::new(In(vec![value!(1), value!(2), value!(3)])));
+    let filter = bson!({ "a" => { "$in" => [1i64, 2i64, 3i64] } });
+    assert_eq!(condition_to_filter(condition), filter);
+
+    let condition = Key(str!("b"), Box::new(Matches(::regex::Regex::new("^hel+o$").unwrap())));
+    let filter = bson!({ "b" => { "$regex" => "^hel+o$" } });
+    assert_eq!(condition_to_filter(condition), filter);
+  }
+
+  #[test]
+  fn test_prefix_full_document_fields() {
+    use query::Condition::*;
+    let condition = Or(vec![
+      Key(str!("age"), Box::new(GreaterThan(value!(21)))),
+      And(vec![
+        Key(str!("name"), Box::new(Equal(value!("a")))),
+        Not(Box::new(Key(str!("active"), Box::new(Equal(value!(true))))))
+      ])
+    ]);
+    let match_stage = prefix_full_document_fields(condition_to_filter(condition));
+    let expected = bson!({
+      "$or" => [
+        { "fullDocument.age" => { "$gt" => 21i64 } },
+        {
+          "$and" => [
+            { "fullDocument.name" => { "$eq" => "a" } },
+            { "$not" => { "fullDocument.active" => { "$eq" => true } } }
+          ]
+        }
+      ]
+    });
+    assert_eq!(match_stage, expected);
+  }
+
+  #[test]
+  fn test_document_to_change_event() {
+    let document = doc! {
+      "operationType" => "update",
+      "fullDocument" => { "a" => 1i64 }
+    };
+    let event = document_to_change_event(document).unwrap();
+    assert_eq!(event.kind, ::driver::ChangeKind::Update);
+    assert_eq!(event.object.get("a"), Some(&value!(1)));
+
+    let document = doc! { "operationType" => "delete" };
+    assert!(document_to_change_event(document).is_none());
+
+    let document = doc! { "operationType" => "invalidate", "fullDocument" => { "a" => 1i64 } };
+    assert!(document_to_change_event(document).is_none());
+  }
+
   #[test]
   fn test_sort_rules_to_sort() {
     let sort = vec![
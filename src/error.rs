@@ -3,10 +3,44 @@
 
 #[cfg(test)]
 use regex::Regex;
+use std::collections::{BTreeMap, HashMap};
 use std::io::Error as IOError;
 use std::error::Error as ErrorTrait;
+use std::path::PathBuf;
+use rmp_serde::decode::Error as MsgPackDecodeError;
+use rmp_serde::encode::Error as MsgPackEncodeError;
+use serde_json::Value;
 use serde_json::error::Error as JSONError;
 use serde_yaml::error::Error as YAMLError;
+use json5::Error as JSON5Error;
+use toml::de::Error as TomlDecodeError;
+use toml::ser::Error as TomlEncodeError;
+
+/// A one-based position in a source file, for pointing a user at the exact
+/// spot an error was raised.
+#[derive(PartialEq, Clone, Debug)]
+pub struct Location {
+  /// The file the error was found in, if the error has been tagged with one
+  /// (see `Error::at_file`).
+  pub filename: Option<PathBuf>,
+  /// The one-based line number. `0` means unknown.
+  pub line: usize,
+  /// The one-based column number. `0` means unknown.
+  pub column: usize
+}
+
+impl Location {
+  /// Creates a location with just a line/column, no filename yet—used where
+  /// a format's own error (e.g. a YAML/JSON syntax error) reports a position
+  /// before `from_file` has a chance to tag it with the path it was reading.
+  pub fn new(line: usize, column: usize) -> Self {
+    Location {
+      filename: None,
+      line: line,
+      column: column
+    }
+  }
+}
 
 /// The code of an error. Designed to easily map to [HTTP status codes][1].
 ///
@@ -31,6 +65,38 @@ pub enum ErrorCode {
   NotImplemented = 501
 }
 
+impl ErrorCode {
+  /// The numeric HTTP status code this error code maps to, for the RFC 7807
+  /// `status` member.
+  pub fn status(&self) -> u16 {
+    self.to_owned() as u16
+  }
+
+  /// A short, human-readable summary of the error code, for the RFC 7807
+  /// `title` member. Per the RFC this should stay constant for a given code,
+  /// independent of the specific occurrence (`message` carries that detail).
+  pub fn title(&self) -> &'static str {
+    match *self {
+      ErrorCode::BadRequest => "Bad Request",
+      ErrorCode::Forbidden => "Forbidden",
+      ErrorCode::NotFound => "Not Found",
+      ErrorCode::NotAcceptable => "Not Acceptable",
+      ErrorCode::Conflict => "Conflict",
+      ErrorCode::BadRange => "Range Not Satisfiable",
+      ErrorCode::Internal => "Internal Server Error",
+      ErrorCode::NotImplemented => "Not Implemented"
+    }
+  }
+
+  /// A URI identifying the problem type, for the RFC 7807 `type` member.
+  /// Defaults to `"about:blank"` (meaning the problem is entirely identified
+  /// by its `status`), which is all any code needs until Ardite publishes
+  /// real documentation pages to link instead.
+  pub fn problem_type(&self) -> &'static str {
+    "about:blank"
+  }
+}
+
 /// Any error generated by Ardite or it‘s drivers should be output using this
 /// type. This allows for a comprehensive display of the error when a service
 /// reports it to the user.
@@ -58,7 +124,22 @@ pub struct Error {
   message: String,
   /// A hint to the user on what to do next to try and avoid the error
   /// happening again. This is optional.
-  hint: Option<String>
+  hint: Option<String>,
+  /// Arbitrary extra members (e.g. an `instance` pointer, or driver-specific
+  /// fields) to fold into `to_problem_json`'s output, so a service built on
+  /// Ardite can hand its serialized form straight back over HTTP without
+  /// re-mapping it.
+  extensions: HashMap<String, Value>,
+  /// Where in a source file this error was raised, if known.
+  location: Option<Location>,
+  /// Whether retrying the operation that produced this error might succeed,
+  /// e.g. a transient network blip or a driver reporting it isn't currently
+  /// the writable primary. See `Error::is_retryable`.
+  retryable: bool,
+  /// Driver-specific retryability labels (e.g. MongoDB's `RetryableWrite`/
+  /// `TransientTransaction`), for callers that want finer-grained backoff
+  /// behavior than the single `retryable` flag.
+  retryable_labels: Vec<String>
 }
 
 impl Error {
@@ -67,10 +148,57 @@ impl Error {
     Error {
       code: code,
       message: message.into(),
-      hint: hint.map(|string| string.into())
+      hint: hint.map(|string| string.into()),
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
     }
   }
 
+  /// Attaches an extension member that `to_problem_json` will fold into the
+  /// problem document, returning `self` so calls can be chained onto a
+  /// constructor.
+  ///
+  /// # Example
+  /// ```rust
+  /// use ardite::error::Error;
+  /// use serde_json;
+  ///
+  /// let error = Error::not_found("Couldn’t find it.").with_extension("instance", serde_json::Value::String("/people/42".to_owned()));
+  ///
+  /// assert_eq!(error.to_problem_json()["instance"], serde_json::Value::String("/people/42".to_owned()));
+  /// ```
+  pub fn with_extension<S>(mut self, key: S, value: Value) -> Self where S: Into<String> {
+    self.extensions.insert(key.into(), value);
+    self
+  }
+
+  /// Renders this error as an `application/problem+json` document, per
+  /// [RFC 7807][1]: `type` and `title` come from the error code, `status` is
+  /// its numeric HTTP status, `detail` is `message`, and `hint` (if any) and
+  /// every attached extension member are folded in alongside them.
+  ///
+  /// [1]: https://tools.ietf.org/html/rfc7807
+  pub fn to_problem_json(&self) -> Value {
+    let mut problem: BTreeMap<String, Value> = self.extensions.iter().map(|(key, value)| (key.to_owned(), value.to_owned())).collect();
+
+    problem.insert("type".to_owned(), Value::String(self.code.problem_type().to_owned()));
+    problem.insert("title".to_owned(), Value::String(self.code.title().to_owned()));
+    problem.insert("status".to_owned(), Value::U64(self.code.status() as u64));
+    problem.insert("detail".to_owned(), Value::String(self.message.to_owned()));
+
+    if let Some(ref hint) = self.hint {
+      problem.insert("hint".to_owned(), Value::String(hint.to_owned()));
+    }
+
+    if self.retryable {
+      problem.insert("retryable".to_owned(), Value::Bool(true));
+    }
+
+    Value::Object(problem)
+  }
+
   /// Get the code for the error.
   pub fn code(&self) -> ErrorCode {
     self.code.to_owned()
@@ -86,6 +214,52 @@ impl Error {
     self.hint.to_owned()
   }
 
+  /// Get the location the error was raised at, if known.
+  pub fn location(&self) -> Option<Location> {
+    self.location.to_owned()
+  }
+
+  /// Attaches a location, returning `self` so calls can be chained onto a
+  /// constructor. Overwrites any location already set.
+  pub fn with_location(mut self, location: Location) -> Self {
+    self.location = Some(location);
+    self
+  }
+
+  /// Tags the error with the file it came from, without disturbing a
+  /// line/column a format's own parser may have already reported (see
+  /// `From<JSONError>`). Meant for `from_file` functions to call on every
+  /// error they return, so a bare syntax error or validation failure still
+  /// points back at the file it was found in.
+  pub fn at_file(mut self, path: &PathBuf) -> Self {
+    let mut location = self.location.unwrap_or_else(|| Location::new(0, 0));
+    location.filename = Some(path.to_owned());
+    self.location = Some(location);
+    self
+  }
+
+  /// Whether retrying the operation that produced this error might succeed.
+  /// Services can implement backoff-and-retry loops generically against this
+  /// rather than understanding each driver's own error code space.
+  pub fn is_retryable(&self) -> bool {
+    self.retryable
+  }
+
+  /// The driver-specific retryability labels attached to this error (e.g.
+  /// MongoDB's `RetryableWrite`/`TransientTransaction`), if any were.
+  pub fn retryable_labels(&self) -> Vec<String> {
+    self.retryable_labels.to_owned()
+  }
+
+  /// Marks the error as retryable, optionally tagging it with a
+  /// driver-specific label, returning `self` so calls can be chained onto a
+  /// constructor.
+  pub fn with_retryable_label<S>(mut self, label: S) -> Self where S: Into<String> {
+    self.retryable = true;
+    self.retryable_labels.push(label.into());
+    self
+  }
+
   /// Convenience function for saying a thing failed validation using
   /// `ErrorCode::BadRequest`.
   ///
@@ -101,7 +275,57 @@ impl Error {
     Error {
       code: ErrorCode::BadRequest,
       message: message.into(),
-      hint: Some(hint.into())
+      hint: Some(hint.into()),
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+
+  /// Convenience function for saying some input was invalid using
+  /// `ErrorCode::BadRequest`.
+  ///
+  /// # Example
+  /// ```rust
+  /// use ardite::error::{Error, ErrorCode};
+  ///
+  /// let error = Error::invalid("That’s not right.", "Try something else.");
+  ///
+  /// assert_eq!(error, Error::new(ErrorCode::BadRequest, "That’s not right.", Some("Try something else.")));
+  /// ```
+  pub fn invalid<S1, S2>(message: S1, hint: S2) -> Self where S1: Into<String>, S2: Into<String> {
+    Error {
+      code: ErrorCode::BadRequest,
+      message: message.into(),
+      hint: Some(hint.into()),
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+
+  /// Convenience function for saying a resource could not be found using
+  /// `ErrorCode::NotFound`.
+  ///
+  /// # Example
+  /// ```rust
+  /// use ardite::error::{Error, ErrorCode};
+  ///
+  /// let error = Error::not_found("Couldn’t find it.");
+  ///
+  /// assert_eq!(error, Error::new(ErrorCode::NotFound, "Couldn’t find it.", None));
+  /// ```
+  pub fn not_found<S>(message: S) -> Self where S: Into<String> {
+    Error {
+      code: ErrorCode::NotFound,
+      message: message.into(),
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
     }
   }
 
@@ -120,7 +344,36 @@ impl Error {
     Error {
       code: ErrorCode::Internal,
       message: message.into(),
-      hint: None
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+
+  /// Convenience function for an internal error known up front to be worth
+  /// retrying, e.g. a driver reporting a transient failure, using
+  /// `ErrorCode::Internal`.
+  ///
+  /// # Example
+  /// ```rust
+  /// use ardite::error::{Error, ErrorCode};
+  ///
+  /// let error = Error::retryable_internal("Not writable primary.");
+  ///
+  /// assert!(error.is_retryable());
+  /// assert_eq!(error.code(), ErrorCode::Internal);
+  /// ```
+  pub fn retryable_internal<S>(message: S) -> Self where S: Into<String> {
+    Error {
+      code: ErrorCode::Internal,
+      message: message.into(),
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: true,
+      retryable_labels: Vec::new()
     }
   }
 
@@ -140,7 +393,11 @@ impl Error {
     Error {
       code: ErrorCode::NotImplemented,
       message: message.into(),
-      hint: None
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
     }
   }
 
@@ -161,7 +418,11 @@ impl From<IOError> for Error {
     Error {
       code: ErrorCode::Internal,
       message: error.description().to_owned(),
-      hint: None
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
     }
   }
 }
@@ -173,20 +434,98 @@ impl From<JSONError> for Error {
         Error {
           code: ErrorCode::BadRequest,
           message: "Syntax error.".to_owned(),
-          hint: Some(format!("Max sure your JSON syntax is correct around line {} column {}.", line, column))
+          hint: Some(format!("Max sure your JSON syntax is correct around line {} column {}.", line, column)),
+          extensions: HashMap::new(),
+          location: Some(Location::new(line, column)),
+          retryable: false,
+          retryable_labels: Vec::new()
         }
       },
       _ => {
         Error {
           code: ErrorCode::Internal,
           message: error.description().to_owned(),
-          hint: None
+          hint: None,
+          extensions: HashMap::new(),
+          location: None,
+          retryable: false,
+          retryable_labels: Vec::new()
         }
       }
     }
   }
 }
 
+impl From<JSON5Error> for Error {
+  fn from(error: JSON5Error) -> Self {
+    Error {
+      code: ErrorCode::BadRequest,
+      message: format!("{}", error),
+      hint: Some("Make sure your JSON5 syntax is correct.".to_owned()),
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+}
+
+impl From<MsgPackDecodeError> for Error {
+  fn from(error: MsgPackDecodeError) -> Self {
+    Error {
+      code: ErrorCode::BadRequest,
+      message: error.description().to_owned(),
+      hint: Some("Make sure your MessagePack payload is correctly encoded.".to_owned()),
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+}
+
+impl From<MsgPackEncodeError> for Error {
+  fn from(error: MsgPackEncodeError) -> Self {
+    Error {
+      code: ErrorCode::Internal,
+      message: error.description().to_owned(),
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+}
+
+impl From<TomlDecodeError> for Error {
+  fn from(error: TomlDecodeError) -> Self {
+    Error {
+      code: ErrorCode::BadRequest,
+      message: error.description().to_owned(),
+      hint: Some("Make sure your TOML syntax is correct.".to_owned()),
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+}
+
+impl From<TomlEncodeError> for Error {
+  fn from(error: TomlEncodeError) -> Self {
+    Error {
+      code: ErrorCode::Internal,
+      message: error.description().to_owned(),
+      hint: None,
+      extensions: HashMap::new(),
+      location: None,
+      retryable: false,
+      retryable_labels: Vec::new()
+    }
+  }
+}
+
 impl From<YAMLError> for Error {
   fn from(error: YAMLError) -> Self {
     match error {
@@ -194,14 +533,22 @@ impl From<YAMLError> for Error {
         Error {
           code: ErrorCode::BadRequest,
           message: message.to_owned(),
-          hint: Some("Make sure your YAML syntax is correct.".to_owned())
+          hint: Some("Make sure your YAML syntax is correct.".to_owned()),
+          extensions: HashMap::new(),
+          location: None,
+          retryable: false,
+          retryable_labels: Vec::new()
         }
       },
       _ => {
         Error {
           code: ErrorCode::Internal,
           message: error.description().to_owned(),
-          hint: None
+          hint: None,
+          extensions: HashMap::new(),
+          location: None,
+          retryable: false,
+          retryable_labels: Vec::new()
         }
       }
     }
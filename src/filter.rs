@@ -0,0 +1,279 @@
+//! A small recursive-descent parser for a human-writable filter string like
+//! `age >= 21 AND (name = "foo" OR active = true)`, giving a frontend or
+//! HTTP layer a string entry point into `query::Condition` without needing
+//! to build the tree programmatically.
+//!
+//! Comparison operators lower into `Condition`'s equality and ordering
+//! variants: `=`/`==` into `Equal`, `>`/`>=`/`<`/`<=` into the matching
+//! `GreaterThan`/`LessThan` family, and `!=` into a negated `Equal`.
+
+use query::Condition;
+use error::Error;
+use value::Value;
+
+/// Parses `input` into a `Condition` tree. Supports the boolean connectives
+/// `OR`, `AND`, and `NOT` (ascending precedence, `NOT` binding tightest),
+/// parenthesized grouping, dotted key paths (`address.city`, lowered into
+/// nested `Condition::Key`), and string/number/boolean/null literals
+/// compared against a key path with a comparison operator. Keywords and
+/// operators are matched case-insensitively.
+pub fn parse(input: &str) -> Result<Condition, Error> {
+  let tokens = try!(tokenize(input));
+  let mut parser = Parser { input: input, tokens: tokens, position: 0 };
+  let condition = try!(parser.parse_or());
+
+  match parser.peek() {
+    Some(&(_, position)) => Err(parse_error(input, position, "Unexpected trailing input.")),
+    None => Ok(condition)
+  }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+  And,
+  Or,
+  Not,
+  LParen,
+  RParen,
+  Dot,
+  Operator(String),
+  Ident(String),
+  String(String),
+  /// A numeric literal's value, and whether it was written with a `.`
+  /// (`21.5`) rather than as a bare integer (`21`)—`parse_literal` uses this
+  /// to lower it to `Value::I64` or `Value::F64` accordingly, since document
+  /// integers deserialize to `Value::I64` and the two never compare equal or
+  /// ordered against each other (see `value::Value`'s `PartialOrd`).
+  Number(f64, bool),
+  True,
+  False,
+  Null
+}
+
+/// Splits `input` into `(Token, char_position)` pairs, failing with a
+/// position-aware error on an unterminated string or an unrecognized
+/// character.
+fn tokenize(input: &str) -> Result<Vec<(Token, usize)>, Error> {
+  let chars: Vec<char> = input.chars().collect();
+  let mut tokens = Vec::new();
+  let mut i = 0;
+
+  while i < chars.len() {
+    let start = i;
+    let c = chars[i];
+
+    if c.is_whitespace() {
+      i += 1;
+    } else if c == '(' {
+      tokens.push((Token::LParen, start));
+      i += 1;
+    } else if c == ')' {
+      tokens.push((Token::RParen, start));
+      i += 1;
+    } else if c == '.' {
+      tokens.push((Token::Dot, start));
+      i += 1;
+    } else if c == '"' || c == '\'' {
+      let quote = c;
+      i += 1;
+      let mut string = String::new();
+      let mut closed = false;
+
+      while i < chars.len() {
+        if chars[i] == quote {
+          closed = true;
+          i += 1;
+          break;
+        }
+        string.push(chars[i]);
+        i += 1;
+      }
+
+      if !closed {
+        return Err(parse_error(input, start, "Unterminated string literal."));
+      }
+
+      tokens.push((Token::String(string), start));
+    } else if c == '=' || c == '!' || c == '>' || c == '<' {
+      let mut operator = c.to_string();
+      i += 1;
+      if i < chars.len() && chars[i] == '=' {
+        operator.push('=');
+        i += 1;
+      }
+      tokens.push((Token::Operator(operator), start));
+    } else if c.is_digit(10) || (c == '-' && chars.get(i + 1).map_or(false, |c| c.is_digit(10))) {
+      let mut number = c.to_string();
+      i += 1;
+      while i < chars.len() && (chars[i].is_digit(10) || chars[i] == '.') {
+        number.push(chars[i]);
+        i += 1;
+      }
+      let is_float = number.contains('.');
+      let number = try!(number.parse::<f64>().map_err(|_| parse_error(input, start, "Invalid number literal.")));
+      tokens.push((Token::Number(number, is_float), start));
+    } else if c.is_alphabetic() || c == '_' {
+      let mut ident = c.to_string();
+      i += 1;
+      while i < chars.len() && (chars[i].is_alphanumeric() || chars[i] == '_') {
+        ident.push(chars[i]);
+        i += 1;
+      }
+      tokens.push((keyword_or_ident(ident), start));
+    } else {
+      return Err(parse_error(input, start, format!("Unexpected character '{}'.", c)));
+    }
+  }
+
+  Ok(tokens)
+}
+
+fn keyword_or_ident(ident: String) -> Token {
+  match ident.to_lowercase().as_str() {
+    "and" => Token::And,
+    "or" => Token::Or,
+    "not" => Token::Not,
+    "true" => Token::True,
+    "false" => Token::False,
+    "null" => Token::Null,
+    _ => Token::Ident(ident)
+  }
+}
+
+struct Parser<'a> {
+  input: &'a str,
+  tokens: Vec<(Token, usize)>,
+  position: usize
+}
+
+impl<'a> Parser<'a> {
+  fn peek(&self) -> Option<&(Token, usize)> {
+    self.tokens.get(self.position)
+  }
+
+  fn next(&mut self) -> Option<(Token, usize)> {
+    let token = self.tokens.get(self.position).cloned();
+    if token.is_some() {
+      self.position += 1;
+    }
+    token
+  }
+
+  fn parse_or(&mut self) -> Result<Condition, Error> {
+    let mut condition = try!(self.parse_and());
+
+    while let Some(&(Token::Or, _)) = self.peek() {
+      self.next();
+      let rhs = try!(self.parse_and());
+      condition = Condition::Or(vec![condition, rhs]);
+    }
+
+    Ok(condition)
+  }
+
+  fn parse_and(&mut self) -> Result<Condition, Error> {
+    let mut condition = try!(self.parse_not());
+
+    while let Some(&(Token::And, _)) = self.peek() {
+      self.next();
+      let rhs = try!(self.parse_not());
+      condition = Condition::And(vec![condition, rhs]);
+    }
+
+    Ok(condition)
+  }
+
+  fn parse_not(&mut self) -> Result<Condition, Error> {
+    if let Some(&(Token::Not, _)) = self.peek() {
+      self.next();
+      let condition = try!(self.parse_not());
+      Ok(Condition::Not(Box::new(condition)))
+    } else {
+      self.parse_primary()
+    }
+  }
+
+  fn parse_primary(&mut self) -> Result<Condition, Error> {
+    match self.peek().cloned() {
+      Some((Token::LParen, _)) => {
+        self.next();
+        let condition = try!(self.parse_or());
+        match self.next() {
+          Some((Token::RParen, _)) => Ok(condition),
+          Some((_, position)) => Err(parse_error(self.input, position, "Expected a closing ')'.")),
+          None => Err(parse_error(self.input, self.input.len(), "Unbalanced parentheses: expected a closing ')'."))
+        }
+      },
+      Some((Token::Ident(_), position)) => self.parse_comparison(position),
+      Some((_, position)) => Err(parse_error(self.input, position, "Expected a key path or '('.")),
+      None => Err(parse_error(self.input, self.input.len(), "Expected a key path or '(', found end of input."))
+    }
+  }
+
+  fn parse_key_path(&mut self) -> Result<Vec<String>, Error> {
+    let mut path = match self.next() {
+      Some((Token::Ident(ident), _)) => vec![ident],
+      Some((_, position)) => return Err(parse_error(self.input, position, "Expected a key name.")),
+      None => return Err(parse_error(self.input, self.input.len(), "Expected a key name, found end of input."))
+    };
+
+    while let Some(&(Token::Dot, _)) = self.peek() {
+      self.next();
+      match self.next() {
+        Some((Token::Ident(ident), _)) => path.push(ident),
+        Some((_, position)) => return Err(parse_error(self.input, position, "Expected a key name after '.'.")),
+        None => return Err(parse_error(self.input, self.input.len(), "Expected a key name after '.', found end of input."))
+      }
+    }
+
+    Ok(path)
+  }
+
+  fn parse_comparison(&mut self, start: usize) -> Result<Condition, Error> {
+    let path = try!(self.parse_key_path());
+
+    let operator = match self.next() {
+      Some((Token::Operator(operator), _)) => operator,
+      Some((_, position)) => return Err(parse_error(self.input, position, "Expected a comparison operator.")),
+      None => return Err(parse_error(self.input, self.input.len(), "Expected a comparison operator, found end of input."))
+    };
+
+    let value = try!(self.parse_literal());
+    let condition = try!(comparison_condition(self.input, start, &operator, value));
+
+    Ok(path.into_iter().rev().fold(condition, |condition, key| Condition::Key(key, Box::new(condition))))
+  }
+
+  fn parse_literal(&mut self) -> Result<Value, Error> {
+    match self.next() {
+      Some((Token::String(string), _)) => Ok(Value::String(string)),
+      Some((Token::Number(number, true), _)) => Ok(Value::F64(number)),
+      Some((Token::Number(number, false), _)) => Ok(Value::I64(number as i64)),
+      Some((Token::True, _)) => Ok(Value::Boolean(true)),
+      Some((Token::False, _)) => Ok(Value::Boolean(false)),
+      Some((Token::Null, _)) => Ok(Value::Null(())),
+      Some((_, position)) => Err(parse_error(self.input, position, "Expected a string, number, boolean, or null literal.")),
+      None => Err(parse_error(self.input, self.input.len(), "Expected a literal, found end of input."))
+    }
+  }
+}
+
+/// Lowers a key path's comparison operator and literal into a `Condition`.
+fn comparison_condition(input: &str, position: usize, operator: &str, value: Value) -> Result<Condition, Error> {
+  match operator {
+    "=" | "==" => Ok(Condition::Equal(value)),
+    "!=" => Ok(Condition::Not(Box::new(Condition::Equal(value)))),
+    ">" => Ok(Condition::GreaterThan(value)),
+    ">=" => Ok(Condition::GreaterThanOrEqual(value)),
+    "<" => Ok(Condition::LessThan(value)),
+    "<=" => Ok(Condition::LessThanOrEqual(value)),
+    _ => Err(parse_error(input, position, format!("Unknown comparison operator '{}'.", operator)))
+  }
+}
+
+fn parse_error<S>(input: &str, position: usize, message: S) -> Error where S: Into<String> {
+  Error::invalid(
+    format!("{} (at position {} in `{}`)", message.into(), position, input),
+    "Check the filter string for unbalanced parentheses, unknown operators, or a malformed literal."
+  )
+}
@@ -0,0 +1,265 @@
+//! Projects an Ardite Schema Definition into a GraphQL SDL schema, and
+//! translates the arguments a generated query field receives back into the
+//! Ardite types a driver already understands.
+//!
+//! Schema generation walks the `SerdeDefinition`/`SerdeSchema` intermediary
+//! tree (see `definition::serde`)—the same concrete tree `codegen::generate`
+//! walks—since the live `schema::Schema` has no getters to introspect a
+//! built definition back out. Argument building walks `query::{Condition,
+//! Sort}`, since those are the types whose shape (`eq`/`in`/`gt`/`lt`
+//! conditions, `and`/`or`/`not` composition) matches the `filter` input
+//! object described for this subsystem. `resolve_collection` is the
+//! executor that ties it all together: it runs the built `Condition`/`Sort`/
+//! `Range` through a `Service`'s driver and projects each result down to the
+//! fields the query actually selected.
+
+use case::Case;
+use error::Error;
+use query::{Condition, Range, Sort};
+use definition::serde::{SerdeDefinition, SerdeSchema};
+use service::Service;
+use value::{Object, Value};
+
+/// Generates a GraphQL SDL document for `definition`: one object type (or
+/// enum) per top-level property of the definition's root object, plus a
+/// `Query` root type with a list field per `object`-typed schema, each
+/// taking `filter`, `orderBy`, `limit`, and `offset` arguments.
+pub fn generate_schema(definition: &SerdeDefinition) -> String {
+  let mut sdl = String::new();
+  let mut query_fields = Vec::new();
+
+  for (name, schema) in definition.data.properties.clone().unwrap_or_default() {
+    let type_name = Case::Class.to_case(name.clone());
+    sdl.push_str(&generate_type(&type_name, &schema));
+
+    if schema.type_.as_ref().map(String::as_str) == Some("object") {
+      query_fields.push(format!(
+        "  {}(filter: FilterInput, orderBy: [OrderByInput!], limit: Int, offset: Int): [{}!]!\n",
+        Case::Camel.to_case(name.clone()), type_name
+      ));
+    }
+  }
+
+  sdl.push_str("type Query {\n");
+  for field in &query_fields {
+    sdl.push_str(field);
+  }
+  sdl.push_str("}\n");
+  sdl
+}
+
+/// Writes the named GraphQL object type, enum, or nothing (for a scalar,
+/// which is only ever referenced inline) for `schema`.
+fn generate_type(name: &str, schema: &SerdeSchema) -> String {
+  if schema.type_.as_ref().map(String::as_str) == Some("object") {
+    generate_object_type(name, schema)
+  } else if schema.type_.is_none() {
+    match schema.enum_ {
+      Some(ref symbols) => generate_enum_type(name, symbols),
+      None => String::new()
+    }
+  } else {
+    String::new()
+  }
+}
+
+fn generate_object_type(name: &str, schema: &SerdeSchema) -> String {
+  let required = schema.required.clone().unwrap_or_default();
+  let mut sdl = format!("type {} {{\n", name);
+
+  for (key, property) in schema.properties.clone().unwrap_or_default() {
+    let field_type = scalar_to_graphql_type(&property);
+    let field_type = if required.contains(&key) { format!("{}!", field_type) } else { field_type };
+    sdl.push_str(&format!("  {}: {}\n", Case::Camel.to_case(key), field_type));
+  }
+
+  sdl.push_str("}\n\n");
+  sdl
+}
+
+fn generate_enum_type(name: &str, symbols: &[String]) -> String {
+  let mut sdl = format!("enum {} {{\n", name);
+  for symbol in symbols {
+    sdl.push_str(&format!("  {}\n", Case::Screaming.to_case(symbol.clone())));
+  }
+  sdl.push_str("}\n\n");
+  sdl
+}
+
+/// Maps a schema to the GraphQL type which represents its values. Nested
+/// `object`/`enum` schemas aren't supported inline—the request's properties
+/// should reference a named type generated by `generate_type` instead—so
+/// they fall back to `String`.
+fn scalar_to_graphql_type(schema: &SerdeSchema) -> String {
+  match schema.type_ {
+    Some(ref type_) => match type_.as_str() {
+      "boolean" => "Boolean".to_owned(),
+      "integer" => "Int".to_owned(),
+      "number" => if schema.multiple_of == Some(1.0) { "Int".to_owned() } else { "Float".to_owned() },
+      "string" => "String".to_owned(),
+      "array" => format!("[{}]", schema.items.as_ref().map_or("String".to_owned(), |items| scalar_to_graphql_type(items))),
+      _ => "String".to_owned()
+    },
+    None => "String".to_owned()
+  }
+}
+
+/// Builds a `Condition` from a GraphQL `filter` input object, whose fields
+/// are either `and`/`or`/`not` (composing nested filter inputs) or a
+/// property name whose value is a single-operator object like
+/// `{ age: { gt: 18 } }`.
+pub fn filter_from_input(input: Value) -> Result<Condition, Error> {
+  let object = match input {
+    Value::Object(object) => object,
+    _ => return Err(Error::invalid(
+      "Filter input must be an object.",
+      "Pass an object whose keys are property names or 'and'/'or'/'not'."
+    ))
+  };
+
+  let mut conditions = Vec::new();
+
+  for (key, value) in object {
+    let condition = match key.as_str() {
+      "and" => Condition::And(try!(conditions_from_array(value))),
+      "or" => Condition::Or(try!(conditions_from_array(value))),
+      "not" => Condition::Not(Box::new(try!(filter_from_input(value)))),
+      _ => Condition::Key(key, Box::new(try!(condition_from_input(value))))
+    };
+    conditions.push(condition);
+  }
+
+  Ok(if conditions.len() == 1 { conditions.pop().unwrap() } else { Condition::And(conditions) })
+}
+
+fn conditions_from_array(value: Value) -> Result<Vec<Condition>, Error> {
+  match value {
+    Value::Array(array) => array.into_iter().map(filter_from_input).collect(),
+    _ => Err(Error::invalid(
+      "'and'/'or' must be an array of filter inputs.",
+      "Pass an array of nested filter objects."
+    ))
+  }
+}
+
+fn condition_from_input(value: Value) -> Result<Condition, Error> {
+  let object = match value {
+    Value::Object(object) => object,
+    _ => return Err(Error::invalid(
+      "A property's filter condition must be an object.",
+      "Pass an object like `{ eq: 42 }` or `{ gt: 18 }`."
+    ))
+  };
+
+  match object.into_iter().next() {
+    Some((operator, operator_value)) => match operator.as_str() {
+      "eq" => Ok(Condition::Equal(operator_value)),
+      "in" => match operator_value {
+        Value::Array(values) => Ok(Condition::In(values)),
+        _ => Err(Error::invalid("'in' must be an array.", "Pass an array of values to match against."))
+      },
+      "gt" => Ok(Condition::GreaterThan(operator_value)),
+      "lt" => Ok(Condition::LessThan(operator_value)),
+      _ => Err(Error::invalid(
+        format!("Unrecognized filter operator '{}'.", operator),
+        "Use one of 'eq', 'in', 'gt', or 'lt'."
+      ))
+    },
+    None => Err(Error::invalid(
+      "A property's filter condition must have exactly one operator.",
+      "Use one of 'eq', 'in', 'gt', or 'lt'."
+    ))
+  }
+}
+
+/// Builds a `Sort` list from a GraphQL `orderBy` argument: an array of
+/// single-key objects like `{ createdAt: "DESC" }`.
+pub fn ordering_from_input(input: Value) -> Result<Vec<Sort>, Error> {
+  match input {
+    Value::Array(entries) => entries.into_iter().map(sort_from_input).collect(),
+    _ => Err(Error::invalid("'orderBy' must be an array.", "Pass an array of single-property ordering objects."))
+  }
+}
+
+fn sort_from_input(entry: Value) -> Result<Sort, Error> {
+  let object = match entry {
+    Value::Object(object) => object,
+    _ => return Err(Error::invalid(
+      "Each 'orderBy' entry must be an object.",
+      "Pass an object like `{ createdAt: \"DESC\" }`."
+    ))
+  };
+
+  match object.into_iter().next() {
+    Some((property, direction)) => {
+      let ascending = match direction {
+        Value::String(ref direction) if direction.eq_ignore_ascii_case("asc") => true,
+        Value::String(ref direction) if direction.eq_ignore_ascii_case("desc") => false,
+        _ => return Err(Error::invalid(
+          "Ordering direction must be 'ASC' or 'DESC'.",
+          "Use 'ASC' or 'DESC' as the direction."
+        ))
+      };
+      Ok(Sort::new(vec![property], ascending))
+    },
+    None => Err(Error::invalid(
+      "Each 'orderBy' entry must have exactly one property.",
+      "Pass an object like `{ createdAt: \"DESC\" }`."
+    ))
+  }
+}
+
+/// Builds a `Range` from GraphQL `limit`/`offset` arguments.
+pub fn range_from_input(limit: Option<u32>, offset: Option<u32>) -> Range {
+  Range::new(offset.map(|offset| offset as usize), limit.map(|limit| limit as usize))
+}
+
+/// Resolves one of a query's list fields: builds the `Condition`/`Sort`/
+/// `Range` the field's `filter`/`orderBy`/`limit`/`offset` arguments
+/// describe, reads `collection` through `service`'s driver with them, and
+/// projects each returned object down to just the properties named in
+/// `selection`—GraphQL's selection set, i.e. the sub-fields the query asked
+/// for on this field.
+///
+/// An empty `selection` (a query with no sub-fields, which GraphQL itself
+/// disallows for an object-typed field) returns each object in full rather
+/// than an all-but-meaningless empty one.
+pub fn resolve_collection(
+  service: &Service,
+  collection: &str,
+  selection: &[String],
+  filter: Option<Value>,
+  order_by: Option<Value>,
+  limit: Option<u32>,
+  offset: Option<u32>
+) -> Result<Value, Error> {
+  let condition = match filter {
+    Some(filter) => try!(filter_from_input(filter)),
+    None => Condition::True
+  };
+
+  let sorts = match order_by {
+    Some(order_by) => try!(ordering_from_input(order_by)),
+    None => Vec::new()
+  };
+
+  let range = range_from_input(limit, offset);
+  let values = try!(service.read(collection, condition, sorts, range));
+  Ok(Value::Array(values.map(|object| project(object, selection)).collect()))
+}
+
+/// Narrows `object` down to just `selection`'s keys, dropping every property
+/// the query didn't ask for.
+fn project(object: Object, selection: &[String]) -> Value {
+  if selection.is_empty() {
+    return Value::Object(object);
+  }
+
+  let mut projected = Object::new();
+  for key in selection {
+    if let Some(value) = object.get(key) {
+      projected.insert(key.clone(), value.clone());
+    }
+  }
+  Value::Object(projected)
+}
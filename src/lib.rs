@@ -5,10 +5,14 @@
 extern crate lazy_static;
 #[macro_use(linear_map)]
 extern crate linear_map;
+extern crate inflections;
+extern crate json5;
 extern crate regex;
+extern crate rmp_serde;
 extern crate serde;
 extern crate serde_json;
 extern crate serde_yaml;
+extern crate toml;
 extern crate url;
 
 #[cfg(feature = "driver_mongodb")]
@@ -20,13 +24,27 @@ extern crate mongodb;
 #[macro_use]
 mod macros;
 
+pub mod case;
+pub mod codegen;
+pub mod definition;
 pub mod driver;
 pub mod error;
+pub mod filter;
+pub mod graphql;
+pub mod patch;
 pub mod query;
+pub mod range;
+pub mod router;
 pub mod schema;
+pub mod service;
+pub mod transaction;
 pub mod value;
 
+pub use definition::Definition;
 pub use driver::Driver;
 pub use error::Error;
-pub use schema::{Definition, Type, DriverConfig, Schema};
-pub use value::{Key, Pointer, Object, Array, Value};
+pub use patch::Patch;
+pub use schema::{Type, Schema};
+pub use schema::Driver as DriverConfig;
+pub use transaction::Transaction;
+pub use value::{Key, Pointer, Object, Array, Value, Format};
@@ -3,7 +3,7 @@
 ///
 /// # Examples
 /// ```rust
-/// # #[macro_use(value)]
+/// # #[macro_use(value, value_internal)]
 /// # extern crate ardite;
 /// use ardite::value::Value;
 ///
@@ -17,7 +17,7 @@
 /// ```
 ///
 /// ```rust
-/// # #[macro_use(value)]
+/// # #[macro_use(value, value_internal)]
 /// # extern crate ardite;
 /// use ardite::value::{Object, Array, Value};
 ///
@@ -49,7 +49,7 @@
 /// ```
 ///
 /// ```rust
-/// # #[macro_use(value)]
+/// # #[macro_use(value, value_internal)]
 /// # extern crate ardite;
 /// use ardite::value::{Object, Array, Value};
 ///
@@ -89,43 +89,144 @@
 /// assert_eq!(value, Value::Object(object));
 /// # }
 /// ```
+///
+/// Array and object positions also accept arbitrary Rust expressions, not
+/// just literals, fed through `Value::from` just like the top-level form:
+///
+/// ```rust
+/// # #[macro_use(value, value_internal)]
+/// # extern crate ardite;
+/// # fn main() {
+/// let user_id = 42i64;
+/// let tags = vec![value!("admin"), value!("staff")];
+/// assert_eq!(value!({ "id" => user_id, "tags" => tags.clone() }), value!({
+///   "id" => 42,
+///   "tags" => ["admin", "staff"]
+/// }));
+/// # }
+/// ```
 #[macro_export]
 macro_rules! value {
   () => {{
-    $crate::value::Value::Null
+    $crate::value::Value::Null(())
   }};
 
-  (()) => {{
-    $crate::value::Value::Null
+  ($($tt:tt)+) => {{
+    $crate::value_internal!($($tt)+)
   }};
+}
+
+/// Implementation detail of the `value!` macro, kept in a separate,
+/// `#[doc(hidden)]` macro so its internal `@array`/`@object` munching rules
+/// don’t clutter `value!`’s own rustdoc. Mirrors the muncher serde_json’s
+/// `json!` macro uses to tell literal sub-structures (`[...]`/`{...}`) apart
+/// from interpolated expressions while walking the input one token tree at a
+/// time.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! value_internal {
+  // Done munging an array, no trailing comma.
+  (@array [$($elems:expr),*]) => {
+    vec![$($elems),*]
+  };
+
+  // Done munging an array, with a trailing comma.
+  (@array [$($elems:expr,)*]) => {
+    vec![$($elems,)*]
+  };
+
+  // Next element is an array.
+  (@array [$($elems:expr,)*] [$($array:tt)*] $($rest:tt)*) => {
+    $crate::value_internal!(@array [$($elems,)* $crate::value_internal!([$($array)*])] $($rest)*)
+  };
+
+  // Next element is an object.
+  (@array [$($elems:expr,)*] {$($object:tt)*} $($rest:tt)*) => {
+    $crate::value_internal!(@array [$($elems,)* $crate::value_internal!({$($object)*})] $($rest)*)
+  };
+
+  // Next element is an expression followed by more elements.
+  (@array [$($elems:expr,)*] $next:expr, $($rest:tt)*) => {
+    $crate::value_internal!(@array [$($elems,)* $crate::value_internal!($next)] $($rest)*)
+  };
+
+  // Last element is an expression with no trailing comma.
+  (@array [$($elems:expr,)*] $last:expr) => {
+    $crate::value_internal!(@array [$($elems,)* $crate::value_internal!($last)])
+  };
 
-  ([]) => {{
+  // Comma after the most recently munched element.
+  (@array [$($elems:expr),*] , $($rest:tt)*) => {
+    $crate::value_internal!(@array [$($elems,)*] $($rest)*)
+  };
+
+  // Done munging an object’s entries.
+  (@object $object:ident () () ()) => {};
+
+  // Insert the current entry (followed by a trailing comma) then continue.
+  (@object $object:ident [$($key:tt)+] ($value:expr) , $($rest:tt)*) => {
+    $object.insert(($($key)+).to_owned(), $value);
+    $crate::value_internal!(@object $object () ($($rest)*) ($($rest)*));
+  };
+
+  // Insert the last entry, with no trailing comma.
+  (@object $object:ident [$($key:tt)+] ($value:expr)) => {
+    $object.insert(($($key)+).to_owned(), $value);
+  };
+
+  // Value is an array.
+  (@object $object:ident ($($key:tt)+) (=> [$($array:tt)*] $($rest:tt)*) $copy:tt) => {
+    $crate::value_internal!(@object $object [$($key)+] ($crate::value_internal!([$($array)*])) $($rest)*);
+  };
+
+  // Value is an object.
+  (@object $object:ident ($($key:tt)+) (=> {$($value:tt)*} $($rest:tt)*) $copy:tt) => {
+    $crate::value_internal!(@object $object [$($key)+] ($crate::value_internal!({$($value)*})) $($rest)*);
+  };
+
+  // Value is an expression followed by more entries.
+  (@object $object:ident ($($key:tt)+) (=> $value:expr , $($rest:tt)*) $copy:tt) => {
+    $crate::value_internal!(@object $object [$($key)+] ($crate::value_internal!($value)) , $($rest)*);
+  };
+
+  // Value is the final expression, with no trailing comma.
+  (@object $object:ident ($($key:tt)+) (=> $value:expr) $copy:tt) => {
+    $crate::value_internal!(@object $object [$($key)+] ($crate::value_internal!($value)));
+  };
+
+  // Munch a key token into the accumulator until `=>` is reached.
+  (@object $object:ident () ($key:tt $($rest:tt)*) $copy:tt) => {
+    $crate::value_internal!(@object $object ($key) ($($rest)*) ($($rest)*));
+  };
+
+  // Entry points, tried in order against the whole macro input.
+  (()) => {
+    $crate::value::Value::Null(())
+  };
+
+  ([]) => {
     $crate::value::Value::Array($crate::value::Array::new())
-  }};
+  };
 
-  ([$($value:tt),*]) => {{
-    let mut array = $crate::value::Array::new();
-    $(
-      array.push(value!($value));
-    )*
-    $crate::value::Value::Array(array)
-  }};
+  ([ $($tt:tt)+ ]) => {
+    $crate::value::Value::Array($crate::value_internal!(@array [] $($tt)+))
+  };
 
-  ({}) => {{
+  ({}) => {
     $crate::value::Value::Object($crate::value::Object::new())
-  }};
+  };
 
-  ({ $($key:expr => $value:tt),* }) => {{
-    let mut object = $crate::value::Object::new();
-    $(
-      object.insert($key.to_owned(), value!($value));
-    )*
-    $crate::value::Value::Object(object)
-  }};
+  ({ $($tt:tt)+ }) => {
+    $crate::value::Value::Object({
+      let mut object = $crate::value::Object::new();
+      $crate::value_internal!(@object object () ($($tt)+) ($($tt)+));
+      object
+    })
+  };
 
-  ($value:expr) => {{
-    $crate::value::Value::from($value)
-  }}
+  ($other:expr) => {
+    $crate::value::Value::from($other)
+  };
 }
 
 #[cfg(test)]
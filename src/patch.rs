@@ -1,11 +1,26 @@
 //! Interfaces to update values in the driver.
 
-use value::{Pointer, Value};
+use value::Value;
 
-/// A single atomic patch on the driver.
+/// A single atomic patch on the driver. Applied to a document by a `Driver`’s
+/// `patch` method, against the pointer-addressed sub-value it names.
 pub enum Patch {
   /// Sets a value at the exact point in the driver.
-  Set(Pointer, Value),
+  Set(Vec<String>, Value),
+  /// Restores the value at the exact point in the driver back to its schema
+  /// default.
+  Reset(Vec<String>),
   /// Removes a value at the exact point in the driver.
-  Remove(Pointer)
+  Remove(Vec<String>)
+}
+
+impl Patch {
+  /// Gets the path this patch will be applied at.
+  pub fn path(&self) -> &[String] {
+    match *self {
+      Patch::Set(ref path, _) => path,
+      Patch::Reset(ref path) => path,
+      Patch::Remove(ref path) => path
+    }
+  }
 }
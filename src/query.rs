@@ -4,11 +4,13 @@
 use std::cmp::Ordering;
 
 use itertools::misc::GenericRange;
+use regex::Regex;
 
-use value::Value;
+use value::{Object, Value};
 
 /// A condition which will resolve to a boolean value after comparing a certain
 /// value with a set rule.
+#[derive(Debug)]
 pub enum Condition {
   /// The condition always passes.
   True,
@@ -26,7 +28,35 @@ pub enum Condition {
   /// `And` condition.
   Key(String, Box<Condition>),
   /// If the compared value is exactly equal to this one, the condition passes.
-  Equal(Value)
+  Equal(Value),
+  /// Passes if the compared value is strictly greater than this one.
+  /// Incomparable types (`partial_cmp` returning `None`) never pass.
+  GreaterThan(Value),
+  /// Passes if the compared value is greater than or equal to this one.
+  /// Incomparable types never pass.
+  GreaterThanOrEqual(Value),
+  /// Passes if the compared value is strictly less than this one.
+  /// Incomparable types never pass.
+  LessThan(Value),
+  /// Passes if the compared value is less than or equal to this one.
+  /// Incomparable types never pass.
+  LessThanOrEqual(Value),
+  /// Passes if the compared value equals any value in this list.
+  In(Vec<Value>),
+  /// Passes if the compared value is a string and matches this regex.
+  Matches(Regex),
+  /// Passes if a full-text search for `query` against the compared value
+  /// matches at least one term. `typo_tolerance` enables matching terms a
+  /// small Levenshtein distance away from a query term rather than requiring
+  /// an exact (or prefix) match. Unlike `Matches`, this isn't limited to
+  /// string values—`SearchRanking::score` recurses into arrays and objects,
+  /// tokenizing every string it finds. Rank matching documents against each
+  /// other with `SearchRanking::score` directly; `is_true` only reports
+  /// whether anything matched.
+  FullTextSearch {
+    query: String,
+    typo_tolerance: bool
+  }
 }
 
 impl Condition {
@@ -40,7 +70,23 @@ impl Condition {
       And(ref conds) => conds.iter().all(|cond| cond.is_true(value)),
       Or(ref conds) => conds.iter().any(|cond| cond.is_true(value)),
       Key(ref key, ref cond) => value.get(key).map_or(false, |value| cond.is_true(value)),
-      Equal(ref other_value) => value == other_value
+      Equal(ref other_value) => value == other_value,
+      GreaterThan(ref other_value) => value.partial_cmp(other_value) == Some(Ordering::Greater),
+      GreaterThanOrEqual(ref other_value) => match value.partial_cmp(other_value) {
+        Some(Ordering::Greater) | Some(Ordering::Equal) => true,
+        _ => false
+      },
+      LessThan(ref other_value) => value.partial_cmp(other_value) == Some(Ordering::Less),
+      LessThanOrEqual(ref other_value) => match value.partial_cmp(other_value) {
+        Some(Ordering::Less) | Some(Ordering::Equal) => true,
+        _ => false
+      },
+      In(ref values) => values.iter().any(|other_value| value == other_value),
+      Matches(ref regex) => match *value {
+        Value::String(ref string) => regex.is_match(string),
+        _ => false
+      },
+      FullTextSearch { ref query, typo_tolerance } => SearchRanking::score(value, query, typo_tolerance).matched_terms > 0
     }
   }
 
@@ -48,6 +94,13 @@ impl Condition {
   pub fn is_false(&self, value: &Value) -> bool {
     !self.is_true(value)
   }
+
+  /// Evaluates the condition against a driver object directly, without
+  /// requiring the caller to wrap it in a `Value::Object` first—the form
+  /// drivers like `Memory` hold their rows in.
+  pub fn is_object_true(&self, object: &Object) -> bool {
+    self.is_true(&Value::Object(object.clone()))
+  }
 }
 
 impl Default for Condition {
@@ -56,7 +109,146 @@ impl Default for Condition {
   }
 }
 
+/// A value's relevance score against a `Condition::FullTextSearch`, used to
+/// rank matching values. Compare two rankings with `compare` to get a
+/// best-first `Ordering`; when it comes back `Equal`, fall back to a `Sort`
+/// list to break the tie.
+///
+/// The ranking is built from four rules, each breaking ties in the last:
+/// number of matched query terms (more is better), proximity (the smallest
+/// span of tokens covering every matched term—tighter is better), typo count
+/// (fewer is better), and exactness (a whole-word match beats a prefix
+/// match).
+#[derive(PartialEq, Debug)]
+pub struct SearchRanking {
+  matched_terms: usize,
+  proximity: Option<usize>,
+  typo_count: usize,
+  whole_word: bool
+}
+
+impl SearchRanking {
+  /// Scores `value` against `query`, the default in-crate implementation
+  /// used by drivers—like `Memory`—that can't execute full-text search
+  /// natively. Tokenizes every string found in `value` (recursing into
+  /// arrays and objects) and matches it against `query`'s own tokens,
+  /// tolerating typos per `typo_tolerance` using the thresholds: a
+  /// Levenshtein distance of at most 1 for terms of 4 or more characters, or
+  /// at most 2 for terms of 8 or more characters.
+  pub fn score(value: &Value, query: &str, typo_tolerance: bool) -> Self {
+    let query_terms = tokenize(query);
+    let mut value_tokens = Vec::new();
+    tokenize_value(value, &mut value_tokens);
+
+    let mut matched_terms = 0;
+    let mut typo_count = 0;
+    let mut whole_word = true;
+    let mut match_positions = Vec::new();
+
+    for query_term in &query_terms {
+      let best_match = value_tokens.iter().enumerate().filter_map(|(index, token)| {
+        if *token == *query_term {
+          Some((index, 0, true))
+        } else if token.starts_with(query_term.as_str()) {
+          Some((index, 0, false))
+        } else if typo_tolerance {
+          let distance = levenshtein_distance(token, query_term);
+          if distance <= typo_threshold(query_term.len()) { Some((index, distance, true)) } else { None }
+        } else {
+          None
+        }
+      }).min_by_key(|&(_, distance, is_whole_word)| (distance, !is_whole_word));
+
+      if let Some((index, distance, is_whole_word)) = best_match {
+        matched_terms += 1;
+        typo_count += distance;
+        whole_word = whole_word && is_whole_word;
+        match_positions.push(index);
+      }
+    }
+
+    let proximity = if match_positions.len() >= 2 {
+      Some(match_positions.iter().max().unwrap() - match_positions.iter().min().unwrap())
+    } else {
+      None
+    };
+
+    SearchRanking {
+      matched_terms: matched_terms,
+      proximity: proximity,
+      typo_count: typo_count,
+      whole_word: whole_word && matched_terms > 0
+    }
+  }
+
+  /// Compares two rankings, best (most relevant) first, applying the four
+  /// rules in order and falling through to the next rule on a tie.
+  pub fn compare(&self, other: &Self) -> Ordering {
+    other.matched_terms.cmp(&self.matched_terms)
+      .then_with(|| {
+        let self_proximity = self.proximity.unwrap_or(usize::max_value());
+        let other_proximity = other.proximity.unwrap_or(usize::max_value());
+        self_proximity.cmp(&other_proximity)
+      })
+      .then_with(|| self.typo_count.cmp(&other.typo_count))
+      .then_with(|| other.whole_word.cmp(&self.whole_word))
+  }
+}
+
+/// Converts the Levenshtein distance-tolerant typo threshold for a query term
+/// of `term_len` characters: no tolerance below 4 characters, at most 1 edit
+/// from 4 characters, and at most 2 edits from 8 characters.
+fn typo_threshold(term_len: usize) -> usize {
+  if term_len >= 8 { 2 } else if term_len >= 4 { 1 } else { 0 }
+}
+
+/// The classic dynamic-programming Levenshtein (edit) distance between two
+/// strings, counting single-character insertions, deletions, and
+/// substitutions.
+fn levenshtein_distance(a: &str, b: &str) -> usize {
+  let a: Vec<char> = a.chars().collect();
+  let b: Vec<char> = b.chars().collect();
+  let mut row: Vec<usize> = (0..b.len() + 1).collect();
+
+  for (i, a_char) in a.iter().enumerate() {
+    let mut previous = row[0];
+    row[0] = i + 1;
+
+    for (j, b_char) in b.iter().enumerate() {
+      let deletion = row[j + 1] + 1;
+      let insertion = row[j] + 1;
+      let substitution = previous + if a_char == b_char { 0 } else { 1 };
+      previous = row[j + 1];
+      row[j + 1] = deletion.min(insertion).min(substitution);
+    }
+  }
+
+  row[b.len()]
+}
+
+/// Splits `text` into lowercase alphanumeric tokens, discarding punctuation
+/// and whitespace.
+fn tokenize(text: &str) -> Vec<String> {
+  text
+  .split(|character: char| !character.is_alphanumeric())
+  .filter(|token| !token.is_empty())
+  .map(str::to_lowercase)
+  .collect()
+}
+
+/// Recursively collects every string `Value` found in `value`—diving into
+/// arrays and objects—into lowercase alphanumeric tokens.
+fn tokenize_value(value: &Value, tokens: &mut Vec<String>) {
+  match *value {
+    Value::String(ref string) => tokens.extend(tokenize(string)),
+    Value::Array(ref array) => for item in array { tokenize_value(item, tokens); },
+    Value::Object(ref object) => for (_, item) in object.clone() { tokenize_value(&item, tokens); },
+    _ => {}
+  }
+}
+
 /// Specifies the order in which a property of a value should be ordered.
+#[derive(Debug)]
 pub struct Sort {
   /// The exacty property to order by.
   property: Vec<String>,
@@ -104,13 +296,54 @@ impl Sort {
   }
 }
 
+/// Compares `a` and `b` by each `Sort` in order, moving to the next one only
+/// when the current one ties. A property that's missing, or whose value is
+/// `Value::Null`, always sorts last regardless of that `Sort`'s direction;
+/// two values this combinator otherwise can't compare (mismatched, unordered
+/// types) are treated as a tie so a later `Sort`—or `Ordering::Equal`, if
+/// every `Sort` ties—can still decide it. Always returns a concrete
+/// `Ordering`, so it's suitable to pass straight to `Vec::sort_by`.
+pub fn compare_by_sorts(sorts: &[Sort], a: &Value, b: &Value) -> Ordering {
+  for sort in sorts {
+    let a_value = a.get_path(&sort.path());
+    let b_value = b.get_path(&sort.path());
+
+    let ordering = match (is_null_like(a_value), is_null_like(b_value)) {
+      (true, true) => Some(Ordering::Equal),
+      (true, false) => return Ordering::Greater,
+      (false, true) => return Ordering::Less,
+      (false, false) => sort.partial_cmp(a, b)
+    };
+
+    match ordering {
+      Some(Ordering::Equal) | None => continue,
+      Some(ordering) => return ordering
+    }
+  }
+
+  Ordering::Equal
+}
+
+/// Whether `value` is a missing property (`None`) or an explicit
+/// `Value::Null`—the two cases `compare_by_sorts` treats identically when
+/// putting nulls last.
+fn is_null_like(value: Option<&Value>) -> bool {
+  match value {
+    None => true,
+    Some(&Value::Null(_)) => true,
+    _ => false
+  }
+}
+
 /// The direction in which an order occurs.
+#[derive(Debug)]
 pub enum Direction {
   Ascending,
   Descending
 }
 
 /// Specifies a positive integer range in a traditional SQL format.
+#[derive(Debug)]
 pub struct Range {
   /// How many items should be included in this range.
   limit: Option<usize>,
@@ -201,5 +434,74 @@ mod tests {
       "hello" => "world",
       "goodbye" => { "moon" => false }
     })));
+    assert!(GreaterThan(value!(10)).is_true(&value!(20)));
+    assert!(GreaterThan(value!(10)).is_false(&value!(10)));
+    assert!(GreaterThan(value!(10)).is_false(&value!("nope")));
+    assert!(GreaterThanOrEqual(value!(10)).is_true(&value!(10)));
+    assert!(GreaterThanOrEqual(value!(10)).is_false(&value!(9)));
+    assert!(LessThan(value!(10)).is_true(&value!(5)));
+    assert!(LessThan(value!(10)).is_false(&value!(10)));
+    assert!(LessThanOrEqual(value!(10)).is_true(&value!(10)));
+    assert!(LessThanOrEqual(value!(10)).is_false(&value!(11)));
+    assert!(In(vec![value!(1), value!(2), value!(3)]).is_true(&value!(2)));
+    assert!(In(vec![value!(1), value!(2), value!(3)]).is_false(&value!(4)));
+    assert!(Matches(::regex::Regex::new("^hel+o$").unwrap()).is_true(&value!("hello")));
+    assert!(Matches(::regex::Regex::new("^hel+o$").unwrap()).is_false(&value!("goodbye")));
+    assert!(Matches(::regex::Regex::new("^hel+o$").unwrap()).is_false(&value!(42)));
+  }
+
+  #[test]
+  fn test_compare_by_sorts() {
+    use std::cmp::Ordering;
+    use super::{Sort, compare_by_sorts};
+
+    let by_group_then_name = vec![
+      Sort::new(vec![str!("group")], true),
+      Sort::new(vec![str!("name")], true)
+    ];
+
+    assert_eq!(compare_by_sorts(&by_group_then_name, &value!({
+      "group" => 1, "name" => "a"
+    }), &value!({
+      "group" => 1, "name" => "b"
+    })), Ordering::Less);
+
+    assert_eq!(compare_by_sorts(&by_group_then_name, &value!({
+      "group" => 2, "name" => "a"
+    }), &value!({
+      "group" => 1, "name" => "z"
+    })), Ordering::Greater);
+
+    assert_eq!(compare_by_sorts(&by_group_then_name, &value!({
+      "group" => 1, "name" => "a"
+    }), &value!({
+      "group" => 1, "name" => "a"
+    })), Ordering::Equal);
+
+    let by_name = vec![Sort::new(vec![str!("name")], true)];
+
+    assert_eq!(compare_by_sorts(&by_name, &value!({
+      "name" => "a"
+    }), &value!({})), Ordering::Less);
+
+    assert_eq!(compare_by_sorts(&by_name, &value!({
+      "name" => ()
+    }), &value!({
+      "name" => "a"
+    })), Ordering::Greater);
+  }
+
+  #[test]
+  fn test_condition_is_object_true() {
+    use super::Condition::*;
+
+    let object = match value!({ "hello" => "world", "age" => 42 }) {
+      ::value::Value::Object(object) => object,
+      _ => unreachable!()
+    };
+
+    assert!(Key(str!("hello"), Box::new(Equal(value!("world")))).is_object_true(&object));
+    assert!(Key(str!("age"), Box::new(GreaterThan(value!(10)))).is_object_true(&object));
+    assert!(!Key(str!("age"), Box::new(LessThan(value!(10)))).is_object_true(&object));
   }
 }
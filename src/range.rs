@@ -1,14 +1,28 @@
-// TODO: Make this it's own module or use another implementation.
+//! A numeric range, and a disjoint, sorted set of them, inspired by
+//! Haskell's [ranged-sets][1]. A `RangeSet` lets a driver combine multiple
+//! numeric `Condition`s on the same key (e.g. `x > 5 AND x < 100 OR x = 200`)
+//! into a normalized set of index ranges it can push down, rather than
+//! scanning and filtering row by row.
+//!
+//! [1]: http://hackage.haskell.org/package/Ranged-sets-0.3.0/docs/Data-Ranged-Ranges.html
 
-/// A numeric range. Inspired by Haskell‘s [ranged-sets][1].
-///
-/// [1]: http://hackage.haskell.org/package/Ranged-sets-0.3.0/docs/Data-Ranged-Ranges.html
+use std::cmp::{max, min};
+use std::u32;
+
+use query::Condition;
+use value::Value;
+
+/// A single contiguous, inclusive numeric range. The first boundary is
+/// always a lower bound (`Above` a value, or `AboveAll`—unbounded below);
+/// the second is always an upper bound (`Below` a value, or
+/// `BelowAll`—unbounded above).
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
 pub struct Range(RangeBoundary, RangeBoundary);
 
 impl Range {
-  /// Creates a new range using an optional limt and offset. If offset is not
-  /// defined, ir will be set to 0.
-  fn new(optional_limit: Option<u32>, optional_offset: Option<u32>) -> Range {
+  /// Creates a new range using an optional limit and offset. If offset is not
+  /// defined, it will be set to 0.
+  pub fn new(optional_limit: Option<u32>, optional_offset: Option<u32>) -> Range {
     let offset = match optional_offset {
       Some(offset) => offset,
       None         => 0
@@ -20,21 +34,106 @@ impl Range {
     })
   }
 
+  /// Creates a range directly from an inclusive lower and upper bound, where
+  /// `None` means unbounded in that direction.
+  fn bounded(lower: Option<u32>, upper: Option<u32>) -> Range {
+    Range(
+      match lower { Some(n) => RangeBoundary::Above(n), None => RangeBoundary::AboveAll },
+      match upper { Some(n) => RangeBoundary::Below(n), None => RangeBoundary::BelowAll }
+    )
+  }
+
+  /// Exposes `bounded` to tests in this crate, which construct `Range`s
+  /// directly from bounds rather than going through `new`'s limit/offset
+  /// shape.
+  #[cfg(test)]
+  pub fn bounded_for_test(lower: Option<u32>, upper: Option<u32>) -> Range {
+    Range::bounded(lower, upper)
+  }
+
   /// Extracts a limit value from the range.
-  fn get_limit(&self) -> Option<u32> {
+  pub fn get_limit(&self) -> Option<u32> {
     match *self {
-      Range(RangeBoundary::Above(from), RangeBoundary::Below(to)) => Some(to - from - 1),
+      Range(RangeBoundary::Above(from), RangeBoundary::Below(to)) => Some(to - from + 1),
       _ => None
     }
   }
 
   /// Extracts an offset value from the range.
-  fn get_offset(&self) -> Option<u32> {
+  pub fn get_offset(&self) -> Option<u32> {
     match *self {
       Range(RangeBoundary::Above(offset), _) => Some(offset),
       _ => None
     }
   }
+
+  /// The inclusive lower bound, or `None` if the range is unbounded below.
+  fn lower(&self) -> Option<u32> {
+    match self.0 {
+      RangeBoundary::Above(n) => Some(n),
+      _ => None
+    }
+  }
+
+  /// The inclusive upper bound, or `None` if the range is unbounded above.
+  fn upper(&self) -> Option<u32> {
+    match self.1 {
+      RangeBoundary::Below(n) => Some(n),
+      _ => None
+    }
+  }
+
+  /// Whether `n` falls within this range.
+  pub fn contains(&self, n: u32) -> bool {
+    self.lower().map_or(true, |lower| n >= lower) && self.upper().map_or(true, |upper| n <= upper)
+  }
+
+  /// Whether this range and `other` overlap, or sit immediately adjacent to
+  /// one another, such that their union would still be one contiguous range.
+  fn touches(&self, other: &Range) -> bool {
+    let self_ends_before_other = match (self.upper(), other.lower()) {
+      (Some(upper), Some(lower)) => upper.saturating_add(1) < lower,
+      _ => false
+    };
+    let other_ends_before_self = match (other.upper(), self.lower()) {
+      (Some(upper), Some(lower)) => upper.saturating_add(1) < lower,
+      _ => false
+    };
+    !self_ends_before_other && !other_ends_before_self
+  }
+
+  /// Merges this range with an overlapping or adjacent `other` into one
+  /// contiguous range. Only meaningful when `self.touches(other)`.
+  fn coalesce(&self, other: &Range) -> Range {
+    let lower = match (self.lower(), other.lower()) {
+      (Some(a), Some(b)) => Some(min(a, b)),
+      _ => None
+    };
+    let upper = match (self.upper(), other.upper()) {
+      (Some(a), Some(b)) => Some(max(a, b)),
+      _ => None
+    };
+    Range::bounded(lower, upper)
+  }
+
+  /// Intersects this range with `other`, or `None` if they don't overlap.
+  fn intersect(&self, other: &Range) -> Option<Range> {
+    let lower = match (self.lower(), other.lower()) {
+      (Some(a), Some(b)) => Some(max(a, b)),
+      (Some(a), None) | (None, Some(a)) => Some(a),
+      (None, None) => None
+    };
+    let upper = match (self.upper(), other.upper()) {
+      (Some(a), Some(b)) => Some(min(a, b)),
+      (Some(a), None) | (None, Some(a)) => Some(a),
+      (None, None) => None
+    };
+
+    match (lower, upper) {
+      (Some(lower), Some(upper)) if lower > upper => None,
+      _ => Some(Range::bounded(lower, upper))
+    }
+  }
 }
 
 pub enum RangeBoundary {
@@ -43,3 +142,260 @@ pub enum RangeBoundary {
   AboveAll,
   BelowAll
 }
+
+impl PartialEq for RangeBoundary {
+  fn eq(&self, other: &RangeBoundary) -> bool {
+    match (self, other) {
+      (&RangeBoundary::Above(a), &RangeBoundary::Above(b)) => a == b,
+      (&RangeBoundary::Below(a), &RangeBoundary::Below(b)) => a == b,
+      (&RangeBoundary::AboveAll, &RangeBoundary::AboveAll) => true,
+      (&RangeBoundary::BelowAll, &RangeBoundary::BelowAll) => true,
+      _ => false
+    }
+  }
+}
+
+impl Eq for RangeBoundary {}
+impl Clone for RangeBoundary { fn clone(&self) -> Self { *self } }
+impl Copy for RangeBoundary {}
+
+impl ::std::fmt::Debug for RangeBoundary {
+  fn fmt(&self, formatter: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+    match *self {
+      RangeBoundary::Above(n) => write!(formatter, "Above({})", n),
+      RangeBoundary::Below(n) => write!(formatter, "Below({})", n),
+      RangeBoundary::AboveAll => write!(formatter, "AboveAll"),
+      RangeBoundary::BelowAll => write!(formatter, "BelowAll")
+    }
+  }
+}
+
+/// A disjoint, sorted set of `Range`s—Ardite's ranged-set. Adjacent or
+/// overlapping ranges are always coalesced on construction, so two
+/// `RangeSet`s built from the same logical set of numbers always compare
+/// equal.
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+pub struct RangeSet(Vec<Range>);
+
+impl RangeSet {
+  /// The empty set, containing no numbers.
+  pub fn new() -> RangeSet {
+    RangeSet(Vec::new())
+  }
+
+  /// A set containing every number in `range`.
+  pub fn from_range(range: Range) -> RangeSet {
+    RangeSet(vec![range])
+  }
+
+  /// Whether `n` is in this set.
+  pub fn contains(&self, n: u32) -> bool {
+    self.0.iter().any(|range| range.contains(n))
+  }
+
+  /// The set of numbers in either `self` or `other`.
+  pub fn union(&self, other: &RangeSet) -> RangeSet {
+    let mut ranges: Vec<Range> = self.0.iter().cloned().chain(other.0.iter().cloned()).collect();
+    RangeSet(normalize(&mut ranges))
+  }
+
+  /// The set of numbers in both `self` and `other`.
+  pub fn intersection(&self, other: &RangeSet) -> RangeSet {
+    let mut ranges = Vec::new();
+
+    for a in &self.0 {
+      for b in &other.0 {
+        if let Some(range) = a.intersect(b) {
+          ranges.push(range);
+        }
+      }
+    }
+
+    RangeSet(normalize(&mut ranges))
+  }
+
+  /// The set of numbers in `self` but not in `other`.
+  pub fn difference(&self, other: &RangeSet) -> RangeSet {
+    let mut ranges = self.0.clone();
+
+    for excluded in &other.0 {
+      ranges = ranges.into_iter().flat_map(|range| subtract(range, excluded)).collect();
+    }
+
+    RangeSet(normalize(&mut ranges))
+  }
+}
+
+/// Sorts `ranges` by lower bound (unbounded-below sorts first, since `None <
+/// Some(_)`) and merges every pair that overlaps or touches, leaving a
+/// minimal, disjoint, sorted set.
+fn normalize(ranges: &mut Vec<Range>) -> Vec<Range> {
+  ranges.sort_by_key(|range| range.lower());
+  let mut normalized: Vec<Range> = Vec::new();
+
+  for range in ranges.drain(..) {
+    match normalized.pop() {
+      Some(last) => if last.touches(&range) {
+        normalized.push(last.coalesce(&range));
+      } else {
+        normalized.push(last);
+        normalized.push(range);
+      },
+      None => normalized.push(range)
+    }
+  }
+
+  normalized
+}
+
+/// Subtracts `excluded` from `range`, returning zero, one, or two remaining
+/// pieces.
+fn subtract(range: Range, excluded: &Range) -> Vec<Range> {
+  let overlap = match range.intersect(excluded) {
+    Some(overlap) => overlap,
+    None => return vec![range]
+  };
+
+  let mut remaining = Vec::new();
+
+  match (range.lower(), overlap.lower()) {
+    (None, Some(m)) if m > 0 => remaining.push(Range::bounded(None, Some(m - 1))),
+    (Some(a), Some(m)) if m > a => remaining.push(Range::bounded(Some(a), Some(m - 1))),
+    _ => {}
+  }
+
+  match (range.upper(), overlap.upper()) {
+    (None, Some(m)) if m < u32::MAX => remaining.push(Range::bounded(Some(m + 1), None)),
+    (Some(b), Some(m)) if m < b => remaining.push(Range::bounded(Some(m + 1), Some(b))),
+    _ => {}
+  }
+
+  remaining
+}
+
+/// Builds a `RangeSet` from a conjunction (`Condition::And`) or disjunction
+/// (`Condition::Or`) of ordering conditions (`GreaterThan`,
+/// `GreaterThanOrEqual`, `LessThan`, `LessThanOrEqual`, `Equal`) found under
+/// `Condition::Key(key, ..)` for the given `key`, so a driver can push the
+/// whole thing down as one normalized set of ranges instead of evaluating
+/// each condition row by row. Returns `None` if `condition` contains
+/// anything this can't represent—a different key, a non-numeric literal,
+/// `Not`, a string `Matches`, and so on.
+pub fn range_set_from_condition(key: &str, condition: &Condition) -> Option<RangeSet> {
+  match *condition {
+    Condition::Key(ref found_key, ref inner) if found_key == key => range_set_from_condition(key, inner),
+    Condition::And(ref conditions) => conditions.iter()
+      .map(|condition| range_set_from_condition(key, condition))
+      .fold(Some(RangeSet::from_range(Range::bounded(None, None))), intersect_option),
+    Condition::Or(ref conditions) => conditions.iter()
+      .map(|condition| range_set_from_condition(key, condition))
+      .fold(Some(RangeSet::new()), union_option),
+    Condition::GreaterThan(ref value) => number(value).map(|n| RangeSet::from_range(Range::bounded(n.checked_add(1), None))),
+    Condition::GreaterThanOrEqual(ref value) => number(value).map(|n| RangeSet::from_range(Range::bounded(Some(n), None))),
+    Condition::LessThan(ref value) => number(value).and_then(|n| if n == 0 {
+      Some(RangeSet::new())
+    } else {
+      Some(RangeSet::from_range(Range::bounded(None, Some(n - 1))))
+    }),
+    Condition::LessThanOrEqual(ref value) => number(value).map(|n| RangeSet::from_range(Range::bounded(None, Some(n)))),
+    Condition::Equal(ref value) => number(value).map(|n| RangeSet::from_range(Range::bounded(Some(n), Some(n)))),
+    _ => None
+  }
+}
+
+fn intersect_option(acc: Option<RangeSet>, range_set: Option<RangeSet>) -> Option<RangeSet> {
+  match (acc, range_set) {
+    (Some(acc), Some(range_set)) => Some(acc.intersection(&range_set)),
+    _ => None
+  }
+}
+
+fn union_option(acc: Option<RangeSet>, range_set: Option<RangeSet>) -> Option<RangeSet> {
+  match (acc, range_set) {
+    (Some(acc), Some(range_set)) => Some(acc.union(&range_set)),
+    _ => None
+  }
+}
+
+/// Extracts a `u32` from a numeric `Value`, the only kind this subsystem's
+/// ranges can represent.
+fn number(value: &Value) -> Option<u32> {
+  match *value {
+    Value::I64(n) if n >= 0 && n <= i64::from(u32::MAX) => Some(n as u32),
+    Value::U64(n) if n <= u64::from(u32::MAX) => Some(n as u32),
+    Value::F64(n) if n >= 0.0 && n.fract() == 0.0 && n <= f64::from(u32::MAX) => Some(n as u32),
+    _ => None
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{Range, RangeSet, range_set_from_condition};
+  use query::Condition;
+  use value::Value;
+
+  #[test]
+  fn test_contains() {
+    let range = Range::new(Some(10), Some(5));
+    assert!(!range.contains(4));
+    assert!(range.contains(5));
+    assert!(range.contains(14));
+    assert!(!range.contains(15));
+  }
+
+  #[test]
+  fn test_union_coalesces_adjacent_ranges() {
+    let a = RangeSet::from_range(Range::bounded_for_test(Some(0), Some(4)));
+    let b = RangeSet::from_range(Range::bounded_for_test(Some(5), Some(9)));
+    let union = a.union(&b);
+    assert!(union.contains(0));
+    assert!(union.contains(9));
+    assert_eq!(union, RangeSet::from_range(Range::bounded_for_test(Some(0), Some(9))));
+  }
+
+  #[test]
+  fn test_intersection() {
+    let a = RangeSet::from_range(Range::bounded_for_test(Some(0), Some(10)));
+    let b = RangeSet::from_range(Range::bounded_for_test(Some(5), Some(20)));
+    let intersection = a.intersection(&b);
+    assert!(!intersection.contains(4));
+    assert!(intersection.contains(5));
+    assert!(intersection.contains(10));
+    assert!(!intersection.contains(11));
+  }
+
+  #[test]
+  fn test_difference() {
+    let a = RangeSet::from_range(Range::bounded_for_test(Some(0), Some(10)));
+    let b = RangeSet::from_range(Range::bounded_for_test(Some(3), Some(5)));
+    let difference = a.difference(&b);
+    assert!(difference.contains(0));
+    assert!(!difference.contains(3));
+    assert!(!difference.contains(5));
+    assert!(difference.contains(6));
+    assert!(difference.contains(10));
+  }
+
+  #[test]
+  fn test_range_set_from_condition() {
+    use query::Condition::*;
+
+    let condition = Or(vec![
+      And(vec![
+        Key(String::from("x"), Box::new(GreaterThan(Value::I64(5)))),
+        Key(String::from("x"), Box::new(LessThan(Value::I64(100))))
+      ]),
+      Key(String::from("x"), Box::new(Equal(Value::I64(200))))
+    ]);
+
+    let range_set = range_set_from_condition("x", &condition).unwrap();
+    assert!(!range_set.contains(5));
+    assert!(range_set.contains(6));
+    assert!(range_set.contains(99));
+    assert!(!range_set.contains(100));
+    assert!(range_set.contains(200));
+    assert!(!range_set.contains(201));
+
+    assert!(range_set_from_condition("y", &condition).is_none());
+  }
+}
@@ -0,0 +1,110 @@
+//! Builds REST route descriptors and partial-update ("Updater") validation
+//! from a loaded `Schema`, so a definition file can drive a working data API
+//! without handwritten endpoints.
+//!
+//! Built against `schema::{Schema, Type}`—the tree actually declared and
+//! exported by the `schema` module. Routing an actual HTTP request needs a
+//! concrete HTTP server crate this corpus doesn't depend on, so this module
+//! stops at producing the route table and validating request bodies; wiring
+//! both into a real server is left to the embedding service.
+
+use error::{Error, ErrorCode};
+use schema::{Schema, Type};
+use value::Value;
+
+/// An HTTP method a generated route responds to.
+#[derive(PartialEq, Debug)]
+pub enum Method {
+  Get,
+  Post,
+  Patch,
+  Delete
+}
+
+/// A single generated REST route: the method and path template it responds
+/// to, and the name of the collection it operates on.
+#[derive(Debug)]
+pub struct Route {
+  pub method: Method,
+  pub path: String,
+  pub collection: String
+}
+
+/// Generates the REST routes for every type in `schema`: list
+/// (`GET /{collection}`, with query-string params mapping to `Filter`,
+/// `Ordering`, and `Range` limit/offset), get (`GET /{collection}/{id}`),
+/// create (`POST /{collection}`), partial update
+/// (`PATCH /{collection}/{id}`, validated via `Updater`), and delete
+/// (`DELETE /{collection}/{id}`).
+pub fn routes(schema: &Schema) -> Vec<Route> {
+  let mut routes = Vec::new();
+
+  for name in schema.types().keys() {
+    routes.push(Route { method: Method::Get, path: format!("/{}", name), collection: name.clone() });
+    routes.push(Route { method: Method::Get, path: format!("/{}/:id", name), collection: name.clone() });
+    routes.push(Route { method: Method::Post, path: format!("/{}", name), collection: name.clone() });
+    routes.push(Route { method: Method::Patch, path: format!("/{}/:id", name), collection: name.clone() });
+    routes.push(Route { method: Method::Delete, path: format!("/{}/:id", name), collection: name.clone() });
+  }
+
+  routes
+}
+
+/// Maps an `Error` to the HTTP status code a router should respond with.
+/// `ErrorCode`'s variants are already numbered to match HTTP status codes
+/// (see its documentation), so this is just a cast—e.g. `NotAcceptable`
+/// becomes 406 and `not_found` (which carries `ErrorCode::NotFound`)
+/// becomes 404.
+pub fn status_code(error: &Error) -> u16 {
+  error.code() as u16
+}
+
+/// The relaxed view of a `Type`'s schema used to validate `PATCH` request
+/// bodies: every property is optional, so a key may be left out of the body
+/// entirely to leave it unchanged. A key present with `Value::Null`
+/// explicitly clears the property, which is already distinct from the key
+/// being absent in a plain JSON object, so no extra wrapper type is needed
+/// around the patch body itself.
+///
+/// Unlike the richer `Collection`/`SchemaObject` vocabulary this module used
+/// to be written against, `Type` has no `additional_properties` toggle, so
+/// every key in the body must be a known property—there's no way to opt a
+/// type into accepting unrecognized ones.
+pub struct Updater<'a> {
+  type_: &'a Type
+}
+
+impl<'a> Updater<'a> {
+  /// Creates an updater for `type_`.
+  pub fn new(type_: &'a Type) -> Self {
+    Updater {
+      type_: type_
+    }
+  }
+
+  /// Validates a `PATCH` request body: every key in `patch` must be a known
+  /// property of the type, but no property is required to be present.
+  /// Per-property value validation is left to the type's own schema once the
+  /// patch is dispatched as a query; this only rejects unknown keys up front.
+  pub fn validate(&self, patch: &Value) -> Result<(), Error> {
+    let object = match *patch {
+      Value::Object(ref object) => object,
+      _ => return Err(Error::invalid(
+        "A partial update body must be an object.",
+        "Send an object whose keys are the properties you want to set or clear."
+      ))
+    };
+
+    for (key, _) in object.clone() {
+      if !self.type_.properties().iter().any(|property| property == &key) {
+        return Err(Error::new(
+          ErrorCode::BadRequest,
+          format!("Unknown property '{}' in partial update body.", key),
+          Some(format!("Remove '{}' from the body.", key))
+        ));
+      }
+    }
+
+    Ok(())
+  }
+}
@@ -49,6 +49,10 @@ impl Type {
       properties: Vec::new()
     }
   }
+
+  pub fn properties(&self) -> &Vec<String> {
+    &self.properties
+  }
 }
 
 /// Configuration for what driver to use and what URL to use to connect that
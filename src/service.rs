@@ -3,9 +3,9 @@ use std::path::PathBuf;
 use error::Error;
 use schema;
 use schema::{Schema, Type};
-use driver::{discover_driver, Driver, Memory};
+use driver::{Driver, DriverRegistry, Iter, Memory};
 use query::{Condition, Sort, Range};
-use value::{Value, Iter};
+use value::Value;
 
 pub fn from_file(path: PathBuf) -> Result<Service, Error> {
   let schema = try!(schema::from_file(path));
@@ -19,9 +19,18 @@ pub struct Service {
 }
 
 impl Service {
+  /// Builds a `Service` from `schema`, connecting its driver (if any) with a
+  /// registry of just the built-in drivers. Use `from_schema_with_registry`
+  /// instead to make a custom, out-of-tree driver available by scheme.
   pub fn from_schema(schema: Schema) -> Result<Self, Error> {
+    Self::from_schema_with_registry(schema, &DriverRegistry::new())
+  }
+
+  /// Builds a `Service` from `schema`, connecting its driver (if any) with
+  /// `registry`.
+  pub fn from_schema_with_registry(schema: Schema, registry: &DriverRegistry) -> Result<Self, Error> {
     let driver = if let Some(driver) = schema.driver() {
-      try!(discover_driver(driver))
+      try!(registry.connect(driver))
     } else {
       Box::new(Memory::new()) as Box<Driver>
     };
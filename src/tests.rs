@@ -50,6 +50,12 @@ macro_rules! vi64 {
   }
 }
 
+macro_rules! vu64 {
+  ($value:expr) => {
+    $crate::value::Value::U64(u64::from($value))
+  }
+}
+
 macro_rules! vf64 {
   ($value:expr) => {
     $crate::value::Value::F64(f64::from($value))
@@ -0,0 +1,106 @@
+//! Interfaces to apply several driver operations as a single atomic unit.
+
+use query::Condition;
+use value::{Object, Pointer, Value};
+
+/// A single operation staged as part of a `Transaction`.
+pub enum TransactionOp {
+  /// Sets the value at a pointer to a value. The pointer’s first key names
+  /// the collection and its second key names the `id` of the document within
+  /// it; the remainder addresses the value within that document the same
+  /// way `Patch::Set`’s path does.
+  Set(Pointer, Value),
+  /// Creates an object within a collection, mirroring `Driver::create`.
+  Insert(String, Object),
+  /// Deletes every value matched by a condition within a collection,
+  /// mirroring `Driver::delete`.
+  Delete(String, Condition)
+}
+
+/// The outcome of a single `TransactionOp`, in the same order the operations
+/// were staged on the `Transaction`.
+pub enum TransactionOpResult {
+  /// The document as it looks after the `Set`.
+  Set(Value),
+  /// The object that was created.
+  Insert(Value),
+  /// The number of values deleted.
+  Delete(u64)
+}
+
+/// Accumulates an ordered list of operations to apply to a `Driver`
+/// atomically through `Driver::apply_transaction`—either every operation
+/// commits, or none of them do.
+#[derive(Default)]
+pub struct Transaction {
+  ops: Vec<TransactionOp>
+}
+
+impl Transaction {
+  /// Creates a new, empty transaction.
+  pub fn new() -> Self {
+    Transaction {
+      ops: Vec::new()
+    }
+  }
+
+  /// Stages a `Set` operation.
+  pub fn set(&mut self, pointer: Pointer, value: Value) -> &mut Self {
+    self.ops.push(TransactionOp::Set(pointer, value));
+    self
+  }
+
+  /// Stages an `Insert` operation.
+  pub fn insert<N: Into<String>>(&mut self, collection: N, object: Object) -> &mut Self {
+    self.ops.push(TransactionOp::Insert(collection.into(), object));
+    self
+  }
+
+  /// Stages a `Delete` operation.
+  pub fn delete<N: Into<String>>(&mut self, collection: N, condition: Condition) -> &mut Self {
+    self.ops.push(TransactionOp::Delete(collection.into(), condition));
+    self
+  }
+
+  /// The staged operations, in the order they’ll be applied.
+  pub fn ops(&self) -> &[TransactionOp] {
+    &self.ops
+  }
+
+  /// Consumes the transaction, returning its staged operations in order.
+  pub fn into_ops(self) -> Vec<TransactionOp> {
+    self.ops
+  }
+}
+
+/// The outcome of a successfully committed `Transaction`: one result per
+/// staged operation, in the same order.
+pub struct TransactionResult(Vec<TransactionOpResult>);
+
+impl TransactionResult {
+  /// Wraps the per-operation results of a committed transaction.
+  pub fn new(results: Vec<TransactionOpResult>) -> Self {
+    TransactionResult(results)
+  }
+
+  /// The per-operation results, in the order the operations were staged.
+  pub fn results(&self) -> &[TransactionOpResult] {
+    &self.0
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use query::Condition;
+  use value::Object;
+
+  #[test]
+  fn test_transaction_builder() {
+    let mut tx = Transaction::new();
+    tx.insert("people", Object::new());
+    tx.delete("people", Condition::True);
+    tx.set(vec![str!("people"), str!("1"), str!("name")], value!("Jane"));
+    assert_eq!(tx.ops().len(), 3);
+  }
+}
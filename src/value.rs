@@ -4,6 +4,7 @@
 //! the driver to these types.
 
 use std::cmp::Ordering;
+use std::ops;
 
 use linear_map;
 use linear_map::LinearMap;
@@ -11,8 +12,29 @@ use serde::ser::{Serialize, Serializer};
 use serde::de::{Deserialize, Deserializer, Error as DeError, Visitor, SeqVisitor, MapVisitor};
 use serde::de::impls::VecVisitor;
 use serde_json;
+use serde_yaml;
 
 use error::Error;
+use patch::Patch;
+
+/// A serialization format a `Value` can be read from or written to. Every
+/// format ultimately goes through `Value`’s own `Serialize`/`Deserialize`
+/// impls, so adding a new format only means adding a variant here and a
+/// matching arm in `Value::from_str`/`Value::to_string` below.
+pub enum Format {
+  /// Plain JSON, via `serde_json`.
+  Json,
+  /// YAML, via `serde_yaml`.
+  Yaml
+}
+
+/// A single property name used to key into an `Object`.
+pub type Key = String;
+
+/// A path of keys identifying a location within a nested `Value` tree, for
+/// example the path to `"moon"` in `{"goodbye": {"moon": true}}` is
+/// `["goodbye", "moon"]`.
+pub type Pointer = Vec<Key>;
 
 /// Ordered representation of a map of key/value pairs, like a JSON object.
 /// Backed by a linear map to maintain order and have high performance for
@@ -24,6 +46,8 @@ impl Object {
   #[inline] pub fn new() -> Self { Object(LinearMap::new()) }
   #[inline] pub fn get(&self, key: &str) -> Option<&Value> { self.0.get(key) }
   #[inline] pub fn insert<K, V>(&mut self, key: K, value: V) -> Option<Value> where K: Into<String>, V: Into<Value> { self.0.insert(key.into(), value.into()) }
+  #[inline] pub fn remove(&mut self, key: &str) -> Option<Value> { self.0.remove(key) }
+  #[inline] pub fn get_mut(&mut self, key: &str) -> Option<&mut Value> { self.0.get_mut(key) }
 
   pub fn map_keys<F>(self, transform: F) -> Object where F: Fn(String) -> String {
     let mut object = Object::new();
@@ -96,6 +120,10 @@ pub enum Value {
   Boolean(bool),
   /// An integer numeric value.
   I64(i64),
+  /// An unsigned integer numeric value too large to fit in an `i64`. Kept
+  /// separate from `I64` (instead of always using `U64`) so the common case
+  /// round-trips through the smaller, signed variant.
+  U64(u64),
   /// A floating point numeric value.
   F64(f64),
   /// A list of characters.
@@ -111,7 +139,7 @@ impl Value {
   ///
   /// # Example
   /// ```rust
-  /// # #[macro_use(value)]
+  /// # #[macro_use(value, value_internal)]
   /// # extern crate ardite;
   /// # fn main() {
   /// assert_eq!(value!(2).get("hello"), None);
@@ -133,7 +161,7 @@ impl Value {
   ///
   /// # Example
   /// ```rust
-  /// # #[macro_use(value)]
+  /// # #[macro_use(value, value_internal)]
   /// # extern crate ardite;
   /// # fn main() {
   /// assert_eq!(value!(2).get_path(&["hello", "world"]), None);
@@ -152,11 +180,68 @@ impl Value {
     path.iter().fold(Some(self), |value, key| value.and_then(|value| value.get(key)))
   }
 
+  /// Gets the value of an object or array variant for a key, mutably.
+  fn get_mut<'a>(&'a mut self, key: &str) -> Option<&'a mut Value> {
+    match *self {
+      Value::Object(ref mut object) => object.get_mut(key),
+      Value::Array(ref mut array) => key.parse::<usize>().ok().and_then(move |index| array.get_mut(index)),
+      _ => None
+    }
+  }
+
+  /// Resolves an [RFC 6901][1] JSON Pointer against this value, returning the
+  /// value found at that pointer, or `None` as soon as any step of the path
+  /// fails to resolve.
+  ///
+  /// A pointer is either the empty string (referring to the whole document)
+  /// or a sequence of `/`-prefixed reference tokens, e.g. `/foo/0/bar`. Each
+  /// token is un-escaped (`~1` to `/`, then `~0` to `~`, in that order to
+  /// avoid double-unescaping) before being resolved against the current node
+  /// exactly like `get`: object lookup by key, array lookup by parsing the
+  /// token as a `usize`.
+  ///
+  /// [1]: https://tools.ietf.org/html/rfc6901
+  ///
+  /// # Example
+  /// ```rust
+  /// # #[macro_use(value, value_internal)]
+  /// # extern crate ardite;
+  /// # fn main() {
+  /// let value = value!({ "foo" => ["bar", "baz"], "a/b" => 1, "m~n" => 2 });
+  /// assert_eq!(value.pointer(""), Some(&value));
+  /// assert_eq!(value.pointer("/foo/0"), Some(&value!("bar")));
+  /// assert_eq!(value.pointer("/a~1b"), Some(&value!(1)));
+  /// assert_eq!(value.pointer("/m~0n"), Some(&value!(2)));
+  /// assert_eq!(value.pointer("/foo/nope"), None);
+  /// # }
+  /// ```
+  pub fn pointer<'a>(&'a self, ptr: &str) -> Option<&'a Value> {
+    if ptr.is_empty() {
+      return Some(self);
+    }
+
+    ptr.split('/').skip(1).map(unescape_pointer_token).fold(Some(self), |value, token| {
+      value.and_then(|value| value.get(&token))
+    })
+  }
+
+  /// Like `pointer`, but resolves to a mutable reference so the sub-value it
+  /// names may be mutated in place.
+  pub fn pointer_mut<'a>(&'a mut self, ptr: &str) -> Option<&'a mut Value> {
+    if ptr.is_empty() {
+      return Some(self);
+    }
+
+    ptr.split('/').skip(1).map(unescape_pointer_token).fold(Some(self), |value, token| {
+      value.and_then(|value| value.get_mut(&token))
+    })
+  }
+
   /// Sets the value of a certain key on an object or array.
   ///
   /// # Example
   /// ```rust
-  /// # #[macro_use(value)]
+  /// # #[macro_use(value, value_internal)]
   /// # extern crate ardite;
   /// # fn main() {
   /// assert!(value!(false).set("hello", value!(true)).is_err());
@@ -197,6 +282,112 @@ impl Value {
     }
   }
 
+  /// Applies a single `Patch` to this value, walking down to the
+  /// pointer-addressed sub-value the patch names and applying its effect
+  /// there: `Set` replaces the sub-value, `Remove` deletes the key, and
+  /// `Reset` restores `default` (typically the schema default for that
+  /// path, resolved by the caller since `Value` has no schema of its own).
+  pub fn apply_patch(self, patch: &Patch, default: &Value) -> Result<Value, Error> {
+    self.apply_patch_at(patch, patch.path(), default)
+  }
+
+  fn apply_patch_at(self, patch: &Patch, path: &[String], default: &Value) -> Result<Value, Error> {
+    if path.is_empty() {
+      return Ok(match *patch {
+        Patch::Set(_, ref new) => new.clone(),
+        Patch::Reset(_) => default.clone(),
+        Patch::Remove(_) => Value::Null(())
+      });
+    }
+
+    let key = &path[0];
+
+    match self {
+      Value::Object(mut object) => {
+        if path.len() == 1 {
+          if let Patch::Remove(_) = *patch {
+            object.remove(key);
+            return Ok(Value::Object(object));
+          }
+        }
+        let child = object.get(key).cloned().unwrap_or(Value::Null(()));
+        let child = try!(child.apply_patch_at(patch, &path[1..], default));
+        object.insert(key.to_owned(), child);
+        Ok(Value::Object(object))
+      },
+      Value::Array(mut array) => {
+        let index = try!(key.parse::<usize>().map_err(|_| Error::invalid(
+          format!("Key '{}' is not a positive integer and can’t be used to patch a value in an array.", key),
+          "Try using a positive integer like 0 as the key."
+        )));
+        let child = array.get(index).cloned().unwrap_or(Value::Null(()));
+        let child = try!(child.apply_patch_at(patch, &path[1..], default));
+        if index < array.len() {
+          array[index] = child;
+          Ok(Value::Array(array))
+        } else {
+          Err(Error::invalid(
+            format!("Can’t patch index {} because it is out of range for array of length {}.", index, array.len()),
+            "Try patching an index inside the array’s bounds."
+          ))
+        }
+      },
+      _ => Err(Error::invalid(
+        format!("Cannot patch path '{}' into primitive value {}.", path.join("/"), self.debug_name()),
+        "Try patching a value which is an object or an array instead."
+      ))
+    }
+  }
+
+  /// Applies an [RFC 7386][1] JSON Merge Patch to this value, returning the
+  /// merged result. If `patch` is an object, each of its keys is merged into
+  /// this value recursively: a `Null` entry removes the corresponding key,
+  /// any other entry is merged into (creating it, and coercing a non-object
+  /// target into an empty object first) the same key of this value. Arrays
+  /// are always replaced wholesale, never merged element-wise. If `patch` is
+  /// not an object, it wholly replaces this value.
+  ///
+  /// [1]: https://tools.ietf.org/html/rfc7386
+  ///
+  /// # Example
+  /// ```rust
+  /// # #[macro_use(value, value_internal)]
+  /// # extern crate ardite;
+  /// # fn main() {
+  /// assert_eq!(value!({ "a" => "b" }).merge(value!({ "a" => "c" })), value!({ "a" => "c" }));
+  /// assert_eq!(value!({ "a" => "b" }).merge(value!({ "b" => "c" })), value!({ "a" => "b", "b" => "c" }));
+  /// assert_eq!(value!({ "a" => "b" }).merge(value!({ "a" => () })), value!({}));
+  /// assert_eq!(value!({ "a" => [1, 2] }).merge(value!({ "a" => [3] })), value!({ "a" => [3] }));
+  /// assert_eq!(value!(42).merge(value!({ "a" => "b" })), value!({ "a" => "b" }));
+  /// assert_eq!(
+  ///   value!({ "a" => { "b" => "c" } }).merge(value!({ "a" => { "b" => (), "c" => "d" } })),
+  ///   value!({ "a" => { "c" => "d" } })
+  /// );
+  /// # }
+  /// ```
+  pub fn merge(self, patch: Value) -> Value {
+    match patch {
+      Value::Object(patch) => {
+        let mut object = match self {
+          Value::Object(object) => object,
+          _ => Object::new()
+        };
+
+        for (key, value) in patch.into_iter() {
+          if let Value::Null(_) = value {
+            object.remove(&key);
+          } else {
+            let merged = object.get(&key).cloned().unwrap_or(Value::Null(())).merge(value);
+            object.insert(key, merged);
+          }
+        }
+
+        Value::Object(object)
+      },
+      patch => patch
+    }
+  }
+
   pub fn map_keys<F>(self, transform: F) -> Value where F: Fn(String) -> String {
     match self {
       Value::Object(object) => Value::Object(object.map_keys(transform)),
@@ -225,14 +416,31 @@ impl Value {
     }
   }
 
+  /// Parses a `Value` from a string in a given `Format`, dispatching to the
+  /// matching serde backend and funneling any error through `Error::from`.
+  pub fn from_str(string: &str, format: Format) -> Result<Value, Error> {
+    match format {
+      Format::Json => serde_json::from_str(string).map_err(Error::from),
+      Format::Yaml => serde_yaml::from_str(string).map_err(Error::from)
+    }
+  }
+
+  /// Serializes this value to a string in a given `Format`.
+  pub fn to_string(&self, format: Format) -> Result<String, Error> {
+    match format {
+      Format::Json => serde_json::to_string(self).map_err(Error::from),
+      Format::Yaml => serde_yaml::to_string(self).map_err(Error::from)
+    }
+  }
+
   /// Creates a `Value` from a JSON string.
   pub fn from_json(json: &str) -> Result<Value, Error> {
-    serde_json::from_str(json).map_err(Error::from)
+    Value::from_str(json, Format::Json)
   }
 
   /// Converts a `Value` into a JSON string.
   pub fn to_json(&self) -> Result<String, Error> {
-    serde_json::to_string(self).map_err(Error::from)
+    Value::to_string(self, Format::Json)
   }
 
   /// Converts a `Value` into a nice and indented JSON string.
@@ -245,6 +453,7 @@ impl Value {
       Value::Null(_) => "null",
       Value::Boolean(_) => "boolean",
       Value::I64(_) => "i64",
+      Value::U64(_) => "u64",
       Value::F64(_) => "f64",
       Value::String(_) => "string",
       Value::Object(_) => "object",
@@ -259,14 +468,23 @@ impl PartialOrd<Value> for Value {
   /// - `Value::Null`
   /// - `Value::Boolean`
   /// - `Value::I64`
+  /// - `Value::U64`
   /// - `Value::F64`
   /// - `Value::String`
+  ///
+  /// `I64` and `U64` also order against each other, since they’re both just
+  /// integers that happened to land on different sides of `i64::MAX` during
+  /// deserialization. The comparison branches on sign first so it never has
+  /// to wrap a negative `i64` into a `u64` (or vice versa).
   fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
     use self::Value::*;
     match (self, other) {
       (&Null(ref a), &Null(ref b)) => a.partial_cmp(b),
       (&Boolean(ref a), &Boolean(ref b)) => a.partial_cmp(b),
       (&I64(ref a), &I64(ref b)) => a.partial_cmp(b),
+      (&U64(ref a), &U64(ref b)) => a.partial_cmp(b),
+      (&I64(a), &U64(b)) => if a < 0 { Some(Ordering::Less) } else { (a as u64).partial_cmp(&b) },
+      (&U64(a), &I64(b)) => if b < 0 { Some(Ordering::Greater) } else { a.partial_cmp(&(b as u64)) },
       (&F64(ref a), &F64(ref b)) => a.partial_cmp(b),
       (&String(ref a), &String(ref b)) => a.partial_cmp(b),
       _ => None
@@ -274,6 +492,57 @@ impl PartialOrd<Value> for Value {
   }
 }
 
+/// Implements `PartialEq` between `Value` and `$ty` (in both directions) by
+/// comparing `$ty` against `self`/`other` after wrapping it in the matching
+/// `Value` variant constructor, `$ctor`. Used below to let assertions and
+/// driver code compare a `Value` against a native Rust type directly, e.g.
+/// `value!(42) == 42`, without reimplementing the comparison for each
+/// direction and type by hand.
+macro_rules! impl_value_partial_eq {
+  ($ty:ty, $ctor:expr) => {
+    impl PartialEq<$ty> for Value {
+      fn eq(&self, other: &$ty) -> bool {
+        self == &$ctor(other.clone())
+      }
+    }
+
+    impl PartialEq<Value> for $ty {
+      fn eq(&self, other: &Value) -> bool {
+        &$ctor(self.clone()) == other
+      }
+    }
+  }
+}
+
+impl_value_partial_eq!(bool, Value::Boolean);
+impl_value_partial_eq!(i64, Value::I64);
+impl_value_partial_eq!(f64, Value::F64);
+impl_value_partial_eq!(String, Value::String);
+
+impl<'a> PartialEq<&'a str> for Value {
+  fn eq(&self, other: &&'a str) -> bool {
+    self == &Value::String((*other).to_owned())
+  }
+}
+
+impl<'a> PartialEq<Value> for &'a str {
+  fn eq(&self, other: &Value) -> bool {
+    &Value::String((*self).to_owned()) == other
+  }
+}
+
+impl PartialEq<()> for Value {
+  fn eq(&self, _other: &()) -> bool {
+    if let Value::Null(_) = *self { true } else { false }
+  }
+}
+
+impl PartialEq<Value> for () {
+  fn eq(&self, other: &Value) -> bool {
+    if let Value::Null(_) = *other { true } else { false }
+  }
+}
+
 impl Serialize for Value {
   #[inline]
   fn serialize<S>(&self, serializer: &mut S) -> Result<(), S::Error> where S: Serializer {
@@ -281,6 +550,7 @@ impl Serialize for Value {
       Value::Null(_) => serializer.serialize_unit(),
       Value::Boolean(value) => serializer.serialize_bool(value),
       Value::I64(value) => serializer.serialize_i64(value),
+      Value::U64(value) => serializer.serialize_u64(value),
       Value::F64(value) => serializer.serialize_f64(value),
       Value::String(ref value) => serializer.serialize_str(&value),
       Value::Array(ref value) => value.serialize(serializer),
@@ -298,7 +568,16 @@ impl Deserialize for Value {
       type Value = Value;
 
       #[inline] fn visit_bool<E>(&mut self, value: bool) -> Result<Value, E> { Ok(Value::Boolean(value)) }
-      #[inline] fn visit_u64<E>(&mut self, value: u64) -> Result<Value, E> { Ok(Value::I64(value as i64)) }
+      // `i64::MAX as u64` is the largest value which still round-trips through
+      // the more common signed variant; anything larger is kept as a `U64` so
+      // it isn’t silently corrupted by wrapping into a negative `i64`.
+      #[inline] fn visit_u64<E>(&mut self, value: u64) -> Result<Value, E> {
+        if value > i64::max_value() as u64 {
+          Ok(Value::U64(value))
+        } else {
+          Ok(Value::I64(value as i64))
+        }
+      }
       #[inline] fn visit_i64<E>(&mut self, value: i64) -> Result<Value, E> { Ok(Value::I64(value)) }
       #[inline] fn visit_f64<E>(&mut self, value: f64) -> Result<Value, E> { Ok(Value::F64(value)) }
       #[inline] fn visit_str<E>(&mut self, value: &str) -> Result<Value, E> where E: DeError { self.visit_string(value.to_owned()) }
@@ -350,6 +629,41 @@ impl From<i64> for Value {
   }
 }
 
+impl From<i32> for Value {
+  /// Unsuffixed integer literals (as used throughout the `value!` macro and
+  /// its callers) default to `i32` when nothing else pins down their type.
+  /// Without this impl, adding `From<u64>` below would leave that default
+  /// with no matching conversion and turn every bare `value!(42)` into an
+  /// ambiguous-type compile error.
+  fn from(number: i32) -> Self {
+    Value::I64(i64::from(number))
+  }
+}
+
+impl From<u32> for Value {
+  fn from(number: u32) -> Self {
+    Value::I64(i64::from(number))
+  }
+}
+
+impl From<u64> for Value {
+  /// Like `visit_u64`, keeps the common case as an `I64` and only falls back
+  /// to `U64` for values which don’t fit in an `i64`.
+  fn from(number: u64) -> Self {
+    if number > i64::max_value() as u64 {
+      Value::U64(number)
+    } else {
+      Value::I64(number as i64)
+    }
+  }
+}
+
+impl From<Array> for Value {
+  fn from(array: Array) -> Self {
+    Value::Array(array)
+  }
+}
+
 impl From<f64> for Value {
   fn from(number: f64) -> Self {
     Value::F64(number)
@@ -374,9 +688,132 @@ impl From<Object> for Value {
   }
 }
 
+/// A type which can be used to index into a `Value`, backing the
+/// `std::ops::Index`/`IndexMut` impls below. This is effectively a private
+/// trait (`pub` only so it can appear in the public `Index`/`IndexMut`
+/// impls—`#[doc(hidden)]` keeps it out of the generated docs), following the
+/// same pattern serde_json uses for `value["key"]`/`value[0]` ergonomics.
+#[doc(hidden)]
+pub trait Index {
+  /// Borrows `value` at `self`, or `None` if it doesn’t exist.
+  fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value>;
+  /// Mutably borrows `value` at `self`, or `None` if it doesn’t exist.
+  fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value>;
+  /// Mutably borrows `value` at `self`, inserting a `Value::Null` first (and
+  /// turning a `Value::Null` target into an empty object) if it doesn’t
+  /// already exist. Panics if `value` is a primitive, or if `self` is an
+  /// out-of-bounds array index.
+  fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value;
+}
+
+impl Index for str {
+  fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+    value.get(self)
+  }
+
+  fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+    value.get_mut(self)
+  }
+
+  fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+    if let Value::Null(_) = *value {
+      *value = Value::Object(Object::new());
+    }
+
+    match *value {
+      Value::Object(ref mut object) => {
+        if object.get(self).is_none() {
+          object.insert(self.to_owned(), Value::Null(()));
+        }
+        object.get_mut(self).unwrap()
+      },
+      _ => panic!("cannot access key \"{}\" in non-object value {:?}", self, value)
+    }
+  }
+}
+
+impl<'a, T: ?Sized> Index for &'a T where T: Index {
+  fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> { (**self).index_into(value) }
+  fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> { (**self).index_into_mut(value) }
+  fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value { (**self).index_or_insert(value) }
+}
+
+impl Index for usize {
+  fn index_into<'v>(&self, value: &'v Value) -> Option<&'v Value> {
+    match *value {
+      Value::Array(ref array) => array.get(*self),
+      _ => None
+    }
+  }
+
+  fn index_into_mut<'v>(&self, value: &'v mut Value) -> Option<&'v mut Value> {
+    match *value {
+      Value::Array(ref mut array) => array.get_mut(*self),
+      _ => None
+    }
+  }
+
+  fn index_or_insert<'v>(&self, value: &'v mut Value) -> &'v mut Value {
+    match *value {
+      Value::Array(ref mut array) => {
+        let length = array.len();
+        if *self >= length {
+          panic!("cannot access index {} of array of length {}", self, length);
+        }
+        &mut array[*self]
+      },
+      _ => panic!("cannot access index {} in non-array value {:?}", self, value)
+    }
+  }
+}
+
+impl<I> ops::Index<I> for Value where I: Index {
+  type Output = Value;
+
+  /// Indexes into a `Value` using a `&str` key or a `usize` array index,
+  /// following serde_json's ergonomics: an object missing the key, or a
+  /// primitive indexed at all, returns `Value::Null` rather than panicking.
+  ///
+  /// # Example
+  /// ```rust
+  /// # #[macro_use(value, value_internal)]
+  /// # extern crate ardite;
+  /// # fn main() {
+  /// let value = value!({ "hello" => ["world"] });
+  /// assert_eq!(value["hello"][0], value!("world"));
+  /// assert_eq!(value["goodbye"], value!());
+  /// assert_eq!(value!(true)["hello"], value!());
+  /// # }
+  /// ```
+  fn index(&self, index: I) -> &Value {
+    static NULL: Value = Value::Null(());
+    index.index_into(self).unwrap_or(&NULL)
+  }
+}
+
+impl<I> ops::IndexMut<I> for Value where I: Index {
+  /// Mutably indexes into a `Value`, auto-inserting `Value::Null` (and
+  /// turning a `Value::Null` target into an empty object) so assignment like
+  /// `value["hello"] = value!(true)` works even when `"hello"` doesn’t exist
+  /// yet. Panics on an out-of-bounds array index, or when indexing into any
+  /// other primitive.
+  fn index_mut(&mut self, index: I) -> &mut Value {
+    index.index_or_insert(self)
+  }
+}
+
+/// Un-escapes a single JSON Pointer reference token, per RFC 6901. Order
+/// matters: `~1` must be decoded to `/` before `~0` is decoded to `~`,
+/// otherwise a token like `~01` (meaning `~1`, a literal tilde followed by a
+/// one) would be corrupted into `/`.
+fn unescape_pointer_token(token: &str) -> String {
+  token.replace("~1", "/").replace("~0", "~")
+}
+
 #[cfg(test)]
 mod tests {
-  use value::Value;
+  use std::cmp::Ordering;
+  use value::{Format, Value};
 
   #[test]
   fn test_get_primitive() {
@@ -434,6 +871,76 @@ mod tests {
     assert_eq!(array.get_path(&["3", "0", "1"]).cloned(), Some(value!(2)));
   }
 
+  #[test]
+  fn test_pointer() {
+    let value = value!({
+      "foo" => ["bar", "baz"],
+      "" => 0,
+      "a/b" => 1,
+      "c%d" => 2,
+      "e^f" => 3,
+      "g|h" => 4,
+      "i\\j" => 5,
+      "k\"l" => 6,
+      " " => 7,
+      "m~n" => 8
+    });
+    assert_eq!(value.pointer(""), Some(&value));
+    assert_eq!(value.pointer("/foo"), Some(&value!(["bar", "baz"])));
+    assert_eq!(value.pointer("/foo/0"), Some(&value!("bar")));
+    assert_eq!(value.pointer("/"), Some(&value!(0)));
+    assert_eq!(value.pointer("/a~1b"), Some(&value!(1)));
+    assert_eq!(value.pointer("/c%d"), Some(&value!(2)));
+    assert_eq!(value.pointer("/m~0n"), Some(&value!(8)));
+    assert_eq!(value.pointer("/foo/nope"), None);
+    assert_eq!(value.pointer("/foo/99"), None);
+    assert_eq!(value!(true).pointer("/hello"), None);
+  }
+
+  #[test]
+  fn test_pointer_mut() {
+    let mut value = value!({ "foo" => ["bar", "baz"] });
+    *value.pointer_mut("/foo/0").unwrap() = value!("replaced");
+    assert_eq!(value, value!({ "foo" => ["replaced", "baz"] }));
+    assert_eq!(value.pointer_mut("/foo/nope"), None);
+  }
+
+  #[test]
+  fn test_index() {
+    let value = value!({
+      "hello" => ["world", "moon"],
+      "goodbye" => {
+        "a" => 1
+      }
+    });
+    assert_eq!(value["hello"], value!(["world", "moon"]));
+    assert_eq!(value["hello"][0], value!("world"));
+    assert_eq!(value["hello"][1], value!("moon"));
+    assert_eq!(value["goodbye"]["a"], value!(1));
+    // Missing keys and out-of-bounds/primitive indexing return `Value::Null`
+    // instead of panicking.
+    assert_eq!(value["nope"], value!());
+    assert_eq!(value["hello"][99], value!());
+    assert_eq!(value!(true)["hello"], value!());
+    assert_eq!(value!(true)[0], value!());
+  }
+
+  #[test]
+  fn test_index_mut() {
+    let mut value = value!({ "hello" => ["world"] });
+    value["hello"][0] = value!("replaced");
+    assert_eq!(value, value!({ "hello" => ["replaced"] }));
+
+    // Indexing a missing object key with `IndexMut` auto-inserts `Null`.
+    value["goodbye"] = value!("moon");
+    assert_eq!(value["goodbye"], value!("moon"));
+
+    // Indexing a `Null` value with `IndexMut` turns it into an object.
+    let mut null = value!();
+    null["hello"] = value!("world");
+    assert_eq!(null, value!({ "hello" => "world" }));
+  }
+
   #[test]
   fn test_set_primitive() {
     assert!(value!().set("hello", value!(true)).is_err());
@@ -500,6 +1007,61 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_u64() {
+    assert_eq!(Value::from_json("18446744073709551615").unwrap(), Value::U64(18446744073709551615));
+    assert_eq!(Value::from_json("18446744073709551615").unwrap().to_json().unwrap(), "18446744073709551615");
+    assert_eq!(Value::from_json("9223372036854775807").unwrap(), value!(9223372036854775807i64));
+    assert_eq!(Value::from(18446744073709551615u64), Value::U64(18446744073709551615));
+    assert_eq!(Value::from(42u64), value!(42));
+    assert_eq!(Value::U64(10).partial_cmp(&value!(5)), Some(Ordering::Greater));
+    assert_eq!(value!(-1).partial_cmp(&Value::U64(10)), Some(Ordering::Less));
+  }
+
+  #[test]
+  fn test_partial_eq_native() {
+    assert_eq!(value!(true), true);
+    assert_eq!(true, value!(true));
+    assert!(value!(true) != false);
+    assert_eq!(value!(42), 42i64);
+    assert_eq!(42i64, value!(42));
+    assert!(value!(42) != 43i64);
+    assert_eq!(value!(3.333), 3.333f64);
+    assert_eq!(3.333f64, value!(3.333));
+    assert!(value!(42) != 42.0f64);
+    assert!(value!(3.333) != 3i64);
+    assert_eq!(value!("hello"), "hello");
+    assert_eq!("hello", value!("hello"));
+    assert_eq!(value!("hello"), "hello".to_owned());
+    assert_eq!("hello".to_owned(), value!("hello"));
+    assert!(value!("hello") != "world");
+    assert_eq!(value!(), ());
+    assert_eq!((), value!());
+    assert!(value!(false) != ());
+  }
+
+  #[test]
+  fn test_merge() {
+    assert_eq!(value!({ "a" => "b" }).merge(value!({ "a" => "c" })), value!({ "a" => "c" }));
+    assert_eq!(value!({ "a" => "b" }).merge(value!({ "b" => "c" })), value!({ "a" => "b", "b" => "c" }));
+    assert_eq!(value!({ "a" => "b" }).merge(value!({ "a" => () })), value!({}));
+    assert_eq!(value!({ "a" => [1, 2] }).merge(value!({ "a" => [3] })), value!({ "a" => [3] }));
+    assert_eq!(value!(42).merge(value!({ "a" => "b" })), value!({ "a" => "b" }));
+    assert_eq!(value!("hello").merge(value!("world")), value!("world"));
+    assert_eq!(
+      value!({ "a" => { "b" => "c" } }).merge(value!({ "a" => { "b" => (), "c" => "d" } })),
+      value!({ "a" => { "c" => "d" } })
+    );
+    assert_eq!(value!(()).merge(value!({ "a" => "b" })), value!({ "a" => "b" }));
+  }
+
+  #[test]
+  fn test_yaml() {
+    let value = value!({ "hello" => "world", "numbers" => [1, 2, 3] });
+    assert_eq!(Value::from_str("hello: world\nnumbers:\n- 1\n- 2\n- 3\n", Format::Yaml).unwrap(), value);
+    assert_eq!(Value::from_str(&value.to_string(Format::Yaml).unwrap(), Format::Yaml).unwrap(), value);
+  }
+
   #[test]
   fn test_to_json_pretty() {
     assert_eq!(
@@ -4,6 +4,8 @@
 //! the database to these types.
 
 use std::collections::BTreeMap;
+use regex::Regex;
+use error::Error;
 use structure::Collection;
 
 /// Represents a JSON pointer to a document property.
@@ -50,11 +52,33 @@ pub enum Filter {
   Condition(Pointer, FilterCondition)
 }
 
+/// A single condition to apply to the value found at a `Filter::Condition`’s
+/// pointer. Each variant lists the SQL and MongoDB construct it lowers to so
+/// a driver implementation has somewhere to start.
 pub enum FilterCondition {
+  /// `=` in SQL, `$eq` in MongoDB.
   Equal(Value),
+  /// `IN (...)` in SQL, `$in` in MongoDB.
   OneOf(Vec<Value>),
+  /// `>` in SQL, `$gt` in MongoDB.
   GreaterThan(Value),
-  LessThan(Value)
+  /// `<` in SQL, `$lt` in MongoDB.
+  LessThan(Value),
+  /// `>=` in SQL, `$gte` in MongoDB.
+  GreaterThanOrEqual(Value),
+  /// `<=` in SQL, `$lte` in MongoDB.
+  LessThanOrEqual(Value),
+  /// `BETWEEN ... AND ...` in SQL, `$gte`/`$lte` in MongoDB. Inclusive of
+  /// both bounds.
+  Between(Value, Value),
+  /// `NOT IN (...)` in SQL, `$nin` in MongoDB.
+  NotOneOf(Vec<Value>),
+  /// `IS [NOT] NULL` in SQL, `$exists` in MongoDB. `true` requires the
+  /// pointer to be present, `false` requires it to be absent.
+  Exists(bool),
+  /// `~` (or the database’s equivalent pattern operator) in SQL, `$regex` in
+  /// MongoDB. Only meaningful against a `Value::String`.
+  Matches(Regex)
 }
 
 /// A single way in which to order a collection of documents.
@@ -65,5 +89,68 @@ pub enum OrderDirection {
   Descending
 }
 
-// TODO: Find a more Rust idiomatic solution for ranges.
-pub struct Range(Option<u32>, Option<u32>);
+/// A single bound of a `Range`. Besides a literal limit or offset, a bound
+/// may be a named variable so a query can be parsed once and executed many
+/// times with the limit/offset bound at execution time, instead of being
+/// baked into the query itself.
+pub enum RangeBound {
+  /// No bound was specified.
+  None,
+  /// A literal bound, known when the query was parsed.
+  Literal(u32),
+  /// A variable bound, resolved to a value when the query is executed.
+  Variable(String)
+}
+
+impl RangeBound {
+  /// Resolves this bound to a concrete natural number, looking the value of
+  /// a `Variable` bound up in `variables` and rejecting anything which is not
+  /// a natural number. `name` (`"offset"` or `"limit"`) is only used to build
+  /// a helpful error message.
+  fn validate(&self, name: &str, variables: &BTreeMap<String, Value>) -> Result<Option<u32>, Error> {
+    match *self {
+      RangeBound::None => Ok(None),
+      RangeBound::Literal(value) => Ok(Some(value)),
+      RangeBound::Variable(ref key) => match variables.get(key) {
+        Some(&Value::Number(number)) if number >= 0.0 && number.fract() == 0.0 => Ok(Some(number as u32)),
+        Some(&Value::Number(number)) => Err(Error::invalid(
+          format!("invalid {} \"{}\": expected natural number", name, number),
+          "Bind this variable to a natural number instead."
+        )),
+        Some(_) => Err(Error::invalid(
+          format!("invalid {} \"${}\": expected natural number", name, key),
+          "Bind this variable to a natural number instead."
+        )),
+        None => Err(Error::invalid(
+          format!("invalid {} \"${}\": undefined variable", name, key),
+          "Provide a value for this variable before executing the query."
+        ))
+      }
+    }
+  }
+}
+
+/// Specifies how many documents to skip (`offset`) and how many to return
+/// (`limit`) out of a larger result set, mirroring a SQL `LIMIT`/`OFFSET` or a
+/// MongoDB `skip`/`limit`. Either bound may be a literal or a variable bound
+/// at execution time; call `validate` before pushing the range down to a
+/// driver so every driver can trust the values it receives instead of
+/// re-checking them itself.
+pub struct Range(RangeBound, RangeBound);
+
+impl Range {
+  /// Creates a new range from an offset and a limit bound.
+  pub fn new(offset: RangeBound, limit: RangeBound) -> Self {
+    Range(offset, limit)
+  }
+
+  /// Validates both bounds of the range, resolving any variable bound
+  /// against `variables`, and returns the plain `(offset, limit)` pair a
+  /// driver can translate directly into its own limit/offset mechanism.
+  pub fn validate(&self, variables: &BTreeMap<String, Value>) -> Result<(Option<u32>, Option<u32>), Error> {
+    Ok((
+      try!(self.0.validate("offset", variables)),
+      try!(self.1.validate("limit", variables))
+    ))
+  }
+}